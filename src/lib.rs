@@ -1,37 +1,126 @@
 // MiNET AI - Miniaturized Neural Evolutionary Topology
 // Version 0.1.0
-use std::usize;
-
 mod minet_display;
-pub use minet_display::*;
 
 mod minet_encoding;
 pub use minet_encoding::*;
 
 mod minet_activation;
-use minet_activation::*;
+pub use minet_activation::Activation;
+
+mod minet_config;
+pub use minet_config::*;
+
+mod minet_neurons;
+
+mod minet_species;
+pub use minet_species::*;
+
+mod minet_population;
+pub use minet_population::*;
+
+mod minet_selection;
+pub use minet_selection::*;
+
+mod minet_evolver;
+pub use minet_evolver::*;
+
+mod minet_checkpoint;
+pub use minet_checkpoint::*;
+
+mod minet_compiled;
+pub use minet_compiled::*;
+
+mod minet_simplify;
+pub use minet_simplify::*;
+
+mod minet_parsimony;
+pub use minet_parsimony::*;
+
+mod minet_nsga2;
+
+mod minet_novelty;
+pub use minet_novelty::*;
+
+mod minet_map_elites;
+pub use minet_map_elites::*;
+
+mod minet_islands;
+pub use minet_islands::*;
+
+mod minet_training;
+pub use minet_training::*;
+
+mod minet_es;
+pub use minet_es::*;
+
+mod minet_cma_es;
+pub use minet_cma_es::*;
+
+mod minet_plasticity;
+pub use minet_plasticity::*;
+
+mod minet_ctrnn;
+pub use minet_ctrnn::*;
+
+#[cfg(feature = "parallel")]
+mod minet_parallel;
 
 use rand::{seq::SliceRandom, thread_rng, Rng};
 use rand_distr::{Distribution, Normal};
 
-#[derive(Clone)]
+/// A single neuron: (bias, outgoing synapses as (target, weight), activation)
+pub type Gene = (f32, Vec<(usize, f32)>, Activation);
+
+#[allow(non_camel_case_types)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct minet {
-    pub genes: Vec<(f32, Vec<(usize, f32)>)>,
+    pub genes: Vec<Gene>,
     pub input: usize,
     pub hidden: usize,
     pub output: usize,
     pub fitness: f32,
+    /// Scores for multi-objective evolution, higher is better in each, see `nsga2_generation`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub objectives: Vec<f32>,
+    /// Behavior descriptor for novelty search, see `NoveltyArchive`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub behavior: Vec<f32>,
+    /// Activation of every neuron from the previous stateful forward pass, read by recurrent synapses
+    pub state: Vec<f32>,
+    /// Evolved Hebbian rule that changes weights during `forward_stateful`, None for fixed weights
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub plasticity: Option<Plasticity>,
+    /// Weight changes learned this lifetime, per neuron and synapse, cleared by `reset_state`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub weight_changes: Vec<Vec<f32>>,
+    /// Time constant of every neuron for continuous-time stepping with `Ctrnn`, empty for ordinary networks
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub time_constants: Vec<f32>,
 }
 
-const INITIAL_WEIGHT_STD_DEVIATION: f32 = 0.5;
-const WEIGHT_STD_DEVIATION: f32 = 0.1;
-const BIAS_STD_DEVIATION: f32 = 0.016;
-const SYNAPSE_PROBABILITY: f64 = 0.4;
-
 impl minet {
     pub fn new(input: usize, hidden: usize, output: usize) -> Self {
+        minet::new_with_config(input, hidden, output, &EvolutionConfig::default())
+    }
+
+    pub fn new_with_config(input: usize, hidden: usize, output: usize, config: &EvolutionConfig) -> Self {
+        minet::new_with_rng(input, hidden, output, config, &mut thread_rng())
+    }
+
+    /// Same as `new_with_config`, drawing all randomness from `rng` so networks are reproducible from a seed
+    pub fn new_with_rng<R: Rng + ?Sized>(
+        input: usize,
+        hidden: usize,
+        output: usize,
+        config: &EvolutionConfig,
+        rng: &mut R,
+    ) -> Self {
         let total_neurons = input + hidden + output;
-        let genes = vec![(0.0, Vec::new()); total_neurons];
+        let genes = (0..total_neurons)
+            .map(|i| (0.0, Vec::new(), default_activation(i, input, hidden)))
+            .collect();
 
         let mut minet = minet {
             genes,
@@ -39,13 +128,19 @@ impl minet {
             hidden,
             output,
             fitness: 0.0,
+            objectives: Vec::new(),
+            behavior: Vec::new(),
+            state: vec![0.0; total_neurons],
+            plasticity: None,
+            weight_changes: Vec::new(),
+            time_constants: Vec::new(),
         };
     
         
         for i in input..total_neurons {
-            minet.connect_random_from(i);
-            minet.connect_random_from(i);
-            minet.connect_random_from(i);
+            for _ in 0..config.initial_synapses_per_neuron {
+                minet.connect_random_from(i, config, rng);
+            }
         }
         
         minet
@@ -53,147 +148,228 @@ impl minet {
     
     /// Initializes the population with random neural networks.
     pub fn initialize_population(pop_size: usize, inputs: usize, hidden: usize, outputs: usize) -> Vec<minet> {
-        (0..pop_size).map(|_| minet::new(inputs, hidden, outputs)).collect()
+        minet::initialize_population_with_config(pop_size, inputs, hidden, outputs, &EvolutionConfig::default())
+    }
+
+    pub fn initialize_population_with_config(
+        pop_size: usize,
+        inputs: usize,
+        hidden: usize,
+        outputs: usize,
+        config: &EvolutionConfig,
+    ) -> Vec<minet> {
+        minet::initialize_population_with_rng(pop_size, inputs, hidden, outputs, config, &mut thread_rng())
     }
 
-    fn mutate(&mut self) {
-        self.mutate_weights();
-        self.mutate_bias();
+    pub fn initialize_population_with_rng<R: Rng + ?Sized>(
+        pop_size: usize,
+        inputs: usize,
+        hidden: usize,
+        outputs: usize,
+        config: &EvolutionConfig,
+        rng: &mut R,
+    ) -> Vec<minet> {
+        (0..pop_size).map(|_| minet::new_with_rng(inputs, hidden, outputs, config, rng)).collect()
+    }
 
-        let mut rng = rand::thread_rng();
-        if rng.gen_bool(SYNAPSE_PROBABILITY) {
-            self.synapse_swap();
+    /// Applies weight, bias and activation mutations, plus any topological mutations the config rolls
+    pub fn mutate(&mut self, config: &EvolutionConfig) {
+        self.mutate_with_rng(config, &mut thread_rng());
+    }
+
+    pub fn mutate_with_rng<R: Rng + ?Sized>(&mut self, config: &EvolutionConfig, rng: &mut R) {
+        // Learned changes belong to the old genome
+        self.weight_changes.clear();
+        self.mutate_weights(config, rng);
+        self.mutate_bias(config, rng);
+        self.mutate_activation(config, rng);
+        self.mutate_plasticity(config, rng);
+        self.mutate_time_constants(config, rng);
+
+        if rng.gen_bool(config.synapse_swap_probability) {
+            self.synapse_swap(config, rng);
+        }
+        if rng.gen_bool(config.add_synapse_probability) {
+            self.synapse_connect_random(config, rng);
+        }
+        if rng.gen_bool(config.remove_synapse_probability) {
+            self.synapse_remove_random(rng);
+        }
+        if rng.gen_bool(config.add_recurrent_synapse_probability) {
+            self.synapse_connect_recurrent(config, rng);
+        }
+        if rng.gen_bool(config.add_neuron_probability) {
+            self.neuron_add_random(config, rng);
+        }
+        if rng.gen_bool(config.remove_neuron_probability) {
+            self.neuron_remove_random(rng);
         }
     }
 
-    fn synapse_swap(&mut self) {
+    fn synapse_swap<R: Rng + ?Sized>(&mut self, config: &EvolutionConfig, rng: &mut R) {
         self.synapse_remove_smallest();
-        self.synapse_connect_random();
+        self.synapse_connect_random(config, rng);
     }
 
     pub fn crossbreed(&self, other: &Self) -> Self {
-        let mut new_genes: Vec<(f32, Vec<(usize, f32)>)> = Vec::new();
-        let parent1 = self.genes.clone();
-        let parent2 = other.genes.clone();
-        let mut rng = rand::thread_rng();
-        for i in 0..parent1.len() {
-            let gene1 = parent1[i].clone();
-            let gene2 = parent2[i].clone();
-            let new_gene = if rng.gen_bool(0.5) { gene1 } else { gene2 };
+        self.crossbreed_with_config(other, &EvolutionConfig::default())
+    }
+
+    pub fn crossbreed_with_config(&self, other: &Self, config: &EvolutionConfig) -> Self {
+        self.crossbreed_with_rng(other, config, &mut thread_rng())
+    }
+
+    /// The child takes this network's layout. When the parents' hidden counts differ, genes taken
+    /// from `other` are remapped onto that layout and synapses into neurons the child lacks are dropped.
+    pub fn crossbreed_with_rng<R: Rng + ?Sized>(&self, other: &Self, config: &EvolutionConfig, rng: &mut R) -> Self {
+        let mut new_genes: Vec<Gene> = Vec::new();
+        let mut time_constants = Vec::with_capacity(self.time_constants.len());
+        for (i, gene1) in self.genes.iter().enumerate() {
+            let take_other = rng.gen_bool(0.5);
+            let new_gene = match self.counterpart_index(i, other) {
+                Some(j) if take_other => {
+                    let (bias, synapses, activation) = &other.genes[j];
+                    let synapses = synapses
+                        .iter()
+                        .filter_map(|&(target, weight)| Some((other.counterpart_index(target, self)?, weight)))
+                        .collect();
+                    (*bias, synapses, *activation)
+                }
+                _ => gene1.clone(),
+            };
             new_genes.push(new_gene);
+            // Time constants travel with their gene, the child is continuous only if this parent is
+            if let Some(&tau) = self.time_constants.get(i) {
+                let theirs = self.counterpart_index(i, other).and_then(|j| other.time_constants.get(j));
+                time_constants.push(match theirs {
+                    Some(&other_tau) if take_other => other_tau,
+                    _ => tau,
+                });
+            }
         }
+        let state = vec![0.0; new_genes.len()];
+        let plasticity = self.crossbreed_plasticity(other, rng);
         let mut child = minet {
             genes: new_genes,
             input: self.input,
             hidden: self.hidden,
             output: self.output,
             fitness: 0.0,
+            objectives: Vec::new(),
+            behavior: Vec::new(),
+            state,
+            plasticity,
+            weight_changes: Vec::new(),
+            time_constants,
         };
-        child.mutate();
+        child.mutate_with_rng(config, rng);
         child
     }
 
-    fn mutate_weights(&mut self) {
+    fn mutate_weights<R: Rng + ?Sized>(&mut self, config: &EvolutionConfig, rng: &mut R) {
         for gene in self.genes.iter_mut() {
             for synapse in gene.1.iter_mut() {
-                synapse.1 += sample_normal(WEIGHT_STD_DEVIATION);
+                if !rng.gen_bool(config.weight_mutation_probability) {
+                    continue;
+                }
+                if rng.gen_bool(config.weight_replace_probability) {
+                    synapse.1 = sample_normal(config.initial_weight_std_dev, rng);
+                } else {
+                    synapse.1 += sample_normal(config.weight_std_dev, rng);
+                }
+                synapse.1 = config.clamp_weight(synapse.1);
             }
         }
     }
 
-    fn mutate_bias(&mut self) {
+    fn mutate_bias<R: Rng + ?Sized>(&mut self, config: &EvolutionConfig, rng: &mut R) {
         for gene in self.genes.iter_mut() {
-            gene.0 += sample_normal(BIAS_STD_DEVIATION);
+            gene.0 += sample_normal(config.bias_std_dev, rng);
+            gene.0 = gene.0.clamp(-config.max_bias, config.max_bias);
         }
     }
 
-    pub fn forward(&self, inputs: Vec<f32>) -> Vec<f32> {
-        let input_neurons = self.input;
-        let hidden_neurons = self.hidden;
-        let output_neurons = self.output;
-        let length = self.genes.len();
-
-        let mut activation_map = vec![0.0; length];
-
-        // Set input activations and propagate forward
-        for i in 0..input_neurons {
-            activation_map[i] = inputs[i];
-            for &(target_idx, weight) in &self.genes[i].1 {
-                activation_map[target_idx as usize] += inputs[i] * weight;
-            }
-        }
-
-        // Process hidden layer
-        for i in input_neurons..(input_neurons + hidden_neurons) {
-            // Add bias
-            activation_map[i] += self.genes[i].0;
-            // Apply ReLU (assume relu(x) = max(0,x))
-            activation_map[i] = tanh(activation_map[i]);
-
-            // Propagate hidden activations forward
-            for &(target_idx, weight) in &self.genes[i].1 {
-                activation_map[target_idx as usize] += activation_map[i] * weight;
+    /// Occasionally swaps the activation of a hidden or output neuron for a random one
+    fn mutate_activation<R: Rng + ?Sized>(&mut self, config: &EvolutionConfig, rng: &mut R) {
+        for gene in self.genes.iter_mut().skip(self.input) {
+            if rng.gen_bool(config.activation_mutation_probability) {
+                gene.2 = *Activation::ALL.choose(rng).unwrap();
             }
         }
+    }
 
-        // Process output layer
-        let mut outputs = Vec::with_capacity(output_neurons);
-        for i in (input_neurons + hidden_neurons)..(input_neurons + hidden_neurons + output_neurons)
-        {
-            // Add bias
-            activation_map[i] += self.genes[i].0;
-            // Apply Sigmoid
-            activation_map[i] = sigmoid(activation_map[i]);
-            outputs.push(activation_map[i]);
-        }
+    /// Runs a forward pass and returns the output activations.
+    /// Recurrent synapses read the stored state, which is left untouched, and plastic weights do not learn.
+    pub fn forward(&self, inputs: Vec<f32>) -> Vec<f32> {
+        let output_start = self.input + self.hidden;
+        self.forward_display(inputs).split_off(output_start)
+    }
+    
+    /// Runs a forward pass and stores every activation as the state read by recurrent synapses on the next call.
+    /// Plastic networks then update their weights with their Hebbian rule.
+    pub fn forward_stateful(&mut self, inputs: Vec<f32>) -> Vec<f32> {
+        let output_start = self.input + self.hidden;
+        let activation_map = self.forward_display(inputs);
+        let outputs = activation_map[output_start..].to_vec();
+        let previous = std::mem::take(&mut self.state);
+        self.apply_plasticity(&activation_map, &previous);
+        self.state = activation_map;
         outputs
     }
     
+    /// Clears the stored activations and learned weight changes, as if no stateful forward pass had run yet
+    pub fn reset_state(&mut self) {
+        self.state = vec![0.0; self.genes.len()];
+        self.weight_changes.clear();
+    }
+    
+    /// Runs a forward pass and returns the activation of every neuron
     pub fn forward_display(&self, inputs: Vec<f32>) -> Vec<f32> {
-        let input_neurons = self.input;
-        let hidden_neurons = self.hidden;
-        let output_neurons = self.output;
-        let length = self.genes.len();
+        let mut activation_map = Vec::new();
+        self.activate_into(&inputs, &mut activation_map);
+        activation_map
+    }
 
-        let mut activation_map = vec![0.0; length];
+    /// Fills `activation_map` with the activation of every neuron, reusing its allocation
+    pub(crate) fn activate_into(&self, inputs: &[f32], activation_map: &mut Vec<f32>) {
+        activation_map.clear();
+        activation_map.resize(self.genes.len(), 0.0);
 
-        // Set input activations and propagate forward
-        for i in 0..input_neurons {
-            activation_map[i] = inputs[i];
-            for &(target_idx, weight) in &self.genes[i].1 {
-                activation_map[target_idx as usize] += inputs[i] * weight;
+        // Recurrent synapses deliver the previous tick's activations
+        for source in 0..self.genes.len() {
+            let previous = self.state.get(source).copied().unwrap_or(0.0);
+            for (target_idx, weight) in self.lifetime_synapses(source) {
+                if target_idx <= source {
+                    activation_map[target_idx] += previous * weight;
+                }
             }
         }
 
-        // Process hidden layer
-        for i in input_neurons..(input_neurons + hidden_neurons) {
-            // Add bias
-            activation_map[i] += self.genes[i].0;
-            // Apply ReLU (assume relu(x) = max(0,x))
-            activation_map[i] = tanh(activation_map[i]);
-
-            // Propagate hidden activations forward
-            for &(target_idx, weight) in &self.genes[i].1 {
-                activation_map[target_idx as usize] += activation_map[i] * weight;
+        // Set input activations and propagate forward
+        for (i, &input) in inputs.iter().enumerate().take(self.input) {
+            activation_map[i] = input;
+            for (target_idx, weight) in self.lifetime_synapses(i) {
+                activation_map[target_idx] += input * weight;
             }
         }
 
-        // Process output layer
-        let mut outputs = Vec::with_capacity(output_neurons);
-        for i in (input_neurons + hidden_neurons)..(input_neurons + hidden_neurons + output_neurons)
-        {
-            // Add bias
-            activation_map[i] += self.genes[i].0;
-            // Apply Sigmoid
-            activation_map[i] = sigmoid(activation_map[i]);
-            outputs.push(activation_map[i]);
+        // Process hidden and output neurons in index order
+        for (i, (bias, _, activation)) in self.genes.iter().enumerate().skip(self.input) {
+            // Add bias and apply the neuron's own activation function
+            let value = activation.apply(activation_map[i] + bias);
+            activation_map[i] = value;
+
+            // Propagate activations forward, recurrent synapses were delivered above
+            for (target_idx, weight) in self.lifetime_synapses(i) {
+                if target_idx > i {
+                    activation_map[target_idx] += value * weight;
+                }
+            }
         }
-        activation_map
     }
     
     /// Removes a random synapse from the genome, if any synapses exist
-    fn synapse_remove_random(&mut self) {
-        let mut rng = rand::thread_rng();
+    fn synapse_remove_random<R: Rng + ?Sized>(&mut self, rng: &mut R) {
         let connected_neurons: Vec<(usize, usize)> = self
             .genes
             .iter()
@@ -201,10 +377,18 @@ impl minet {
             .flat_map(|(i, gene)| gene.1.iter().enumerate().map(move |(j, _)| (i, j)))
             .collect();
 
-        if let Some(&(neuron, synapse)) = connected_neurons.choose(&mut rng) {
-            self.genes[neuron].1.remove(synapse);
-        } else {
-            println!("No synapses to remove.");
+        if let Some(&(neuron, synapse)) = connected_neurons.choose(rng) {
+            self.remove_synapse(neuron, synapse);
+        }
+    }
+
+    /// Removes one synapse along with its learned weight change, keeping `weight_changes` parallel to the genes
+    fn remove_synapse(&mut self, neuron: usize, synapse: usize) {
+        self.genes[neuron].1.remove(synapse);
+        if let Some(changes) = self.weight_changes.get_mut(neuron) {
+            if synapse < changes.len() {
+                changes.remove(synapse);
+            }
         }
     }
     
@@ -228,20 +412,24 @@ impl minet {
         // If we found a valid synapse, remove it
         if min_synapse_location.0 != usize::MAX {
             let (g_idx, s_idx) = min_synapse_location;
-            self.genes[g_idx].1.remove(s_idx);
+            self.remove_synapse(g_idx, s_idx);
         }
     }
     
     /// Connects two random, unconnected neurons in the forward direction. 
-    fn synapse_connect_random(&mut self) {
-        let mut rng = rand::thread_rng();
+    fn synapse_connect_random<R: Rng + ?Sized>(&mut self, config: &EvolutionConfig, rng: &mut R) {
         let non_output = self.input + self.hidden;
 
+        // A fully connected network has nowhere left to grow
+        if (0..non_output).all(|source| self.synapse_candidates(source).is_empty()) {
+            return;
+        }
+
         loop {
             let source = rng.gen_range(0..non_output);
             let target_candidates = self.synapse_candidates(source);
-            if let Some(&target) = target_candidates.choose(&mut rng) {
-                let weight = sample_normal(INITIAL_WEIGHT_STD_DEVIATION);
+            if let Some(&target) = target_candidates.choose(rng) {
+                let weight = config.clamp_weight(sample_normal(config.initial_weight_std_dev, rng));
                 self.genes[source].1.push((target, weight));
                 break;
             }
@@ -250,25 +438,100 @@ impl minet {
     
     /// Connects a random neuron from an index lower than the to_index. 
     /// ie from an output neuron to a hidden or input neuron in the forward direction
-    fn connect_random_from(&mut self, to_index: usize) -> usize {
-        let mut rng = rand::thread_rng();
-        
+    fn connect_random_from<R: Rng + ?Sized>(&mut self, to_index: usize, config: &EvolutionConfig, rng: &mut R) -> usize {
         let non_output = self.input + self.hidden;
         
         let from_index = rng.gen_range(0..(to_index.clamp(0, non_output)));
         
         let synapse_candidates = self.synapse_candidates(from_index);
         
-        for i in 0..synapse_candidates.len() {
-            let target = synapse_candidates[i];
+        for target in synapse_candidates {
             if target == to_index {
-                let weight = sample_normal(INITIAL_WEIGHT_STD_DEVIATION);
+                let weight = config.clamp_weight(sample_normal(config.initial_weight_std_dev, rng));
                 self.genes[from_index].1.push((to_index, weight));
             }
         }
         from_index
     }
     
+    /// Connects a random hidden or output neuron back to itself or a lower hidden or output neuron
+    fn synapse_connect_recurrent<R: Rng + ?Sized>(&mut self, config: &EvolutionConfig, rng: &mut R) {
+        let sources: Vec<usize> = (self.input..self.genes.len())
+            .filter(|&source| !self.synapse_recurrent_candidates(source, config).is_empty())
+            .collect();
+
+        if let Some(&source) = sources.choose(rng) {
+            let target_candidates = self.synapse_recurrent_candidates(source, config);
+            let &target = target_candidates.choose(rng).unwrap();
+            let weight = config.clamp_weight(sample_normal(config.initial_weight_std_dev, rng));
+            self.genes[source].1.push((target, weight));
+        }
+    }
+    
+    /// Generates candidates for recurrent synapses from the source
+    /// 1. Targets are hidden or output neurons with an index no larger than the source
+    /// 2. The source itself is only a candidate when self loops are allowed
+    /// 3. Candidates must not already be connected
+    fn synapse_recurrent_candidates(&self, source: usize, config: &EvolutionConfig) -> Vec<usize> {
+        if source < self.input {
+            return Vec::new();
+        }
+
+        (self.input..=source)
+            .filter(|&i| (i != source || config.allow_self_loops) && !self.synapse_is_connected(source, i))
+            .collect()
+    }
+    
+    /// Returns true if any synapse reads the previous tick's activations
+    pub fn is_recurrent(&self) -> bool {
+        self.genes
+            .iter()
+            .enumerate()
+            .any(|(source, gene)| gene.1.iter().any(|&(target, _)| target <= source))
+    }
+
+    /// Number of values `flatten` returns: one bias per hidden or output neuron plus one weight per synapse
+    pub fn parameter_count(&self) -> usize {
+        self.hidden + self.output + self.synapse_count()
+    }
+
+    /// Every trainable value in genome order: for each neuron its bias, skipped for inputs, then its synapse weights
+    pub fn flatten(&self) -> Vec<f32> {
+        let mut parameters = Vec::with_capacity(self.parameter_count());
+        for (i, (bias, synapses, _)) in self.genes.iter().enumerate() {
+            if i >= self.input {
+                parameters.push(*bias);
+            }
+            parameters.extend(synapses.iter().map(|&(_, weight)| weight));
+        }
+        parameters
+    }
+
+    /// Writes values in `flatten` order back into the genome, leaving the topology unchanged.
+    /// Panics if `parameters` does not hold exactly `parameter_count()` values.
+    pub fn unflatten(&mut self, parameters: &[f32]) {
+        assert_eq!(parameters.len(), self.parameter_count(), "parameter count does not match the genome");
+        let mut values = parameters.iter().copied();
+        for (i, (bias, synapses, _)) in self.genes.iter_mut().enumerate() {
+            if i >= self.input {
+                *bias = values.next().unwrap();
+            }
+            for synapse in synapses.iter_mut() {
+                synapse.1 = values.next().unwrap();
+            }
+        }
+    }
+
+    /// Clamps every weight and bias into the given limits
+    pub(crate) fn clamp_parameters(&mut self, max_weight: f32, max_bias: f32) {
+        for (bias, synapses, _) in self.genes.iter_mut() {
+            *bias = bias.clamp(-max_bias, max_bias);
+            for synapse in synapses.iter_mut() {
+                synapse.1 = synapse.1.clamp(-max_weight, max_weight);
+            }
+        }
+    }
+    
     /// Generates candidates for synapse connections with the given criteria
     /// 1. If source is a output neuron, it returns an empty vec
     /// 2. Target index candidates must be larger than source index
@@ -302,35 +565,65 @@ impl minet {
     /// Randomly crossbreeds them (asexual reproduction is possible)
     /// Returns a new population of the children of the survivors, with mutations
     pub fn crossbreed_population(
-        mut population: Vec<minet>,
+        population: Vec<minet>,
         survival_rate: f32,
         target_population: usize, 
     ) -> Vec<minet> {
-        let population_size = population.len();
-        let surviving_count = (population_size as f32 * survival_rate).round() as usize;
-    
-        let new_target = target_population - surviving_count;
-        
-        // Take the best (survival_rate * population) of the population by fitness
-        population.sort_by(|a, b| b.fitness.partial_cmp(&a.fitness).unwrap());
-        population.truncate(surviving_count);
-        
-        for i in 0..population.len() {
-            population[i].fitness = 0.0;
-        }
+        minet::crossbreed_population_with_config(population, survival_rate, target_population, &EvolutionConfig::default())
+    }
+
+    pub fn crossbreed_population_with_config(
+        population: Vec<minet>,
+        survival_rate: f32,
+        target_population: usize,
+        config: &EvolutionConfig,
+    ) -> Vec<minet> {
+        minet::crossbreed_population_with_rng(population, survival_rate, target_population, config, &mut thread_rng())
+    }
+
+    pub fn crossbreed_population_with_rng<R: Rng + ?Sized>(
+        mut population: Vec<minet>,
+        survival_rate: f32,
+        target_population: usize,
+        config: &EvolutionConfig,
+        rng: &mut R,
+    ) -> Vec<minet> {
+        minet::truncate_to_survivors(&mut population, survival_rate, config);
+        let new_target = target_population - population.len();
         
         for _ in 0..new_target{
-            let parent1 = population.choose(&mut thread_rng()).unwrap();
-            let parent2 = population.choose(&mut thread_rng()).unwrap();
-            let mut child = parent1.crossbreed(parent2);
+            let parent1 = population.choose(rng).unwrap();
+            let parent2 = population.choose(rng).unwrap();
+            let child = parent1.crossbreed_with_rng(parent2, config, rng);
             population.push(child);
         }
         population
     }
+    
+    /// Keeps the best (survival_rate * population) of the population by fitness, with their fitness reset
+    pub(crate) fn truncate_to_survivors(population: &mut Vec<minet>, survival_rate: f32, config: &EvolutionConfig) {
+        let surviving_count = (population.len() as f32 * survival_rate).round() as usize;
+        sort_best_first(population, config.prefer_smaller_on_ties);
+        population.truncate(surviving_count);
+        
+        for network in population.iter_mut() {
+            network.fitness = 0.0;
+        }
+    }
+}
+
+/// Inputs pass through unchanged, hidden neurons use tanh and outputs use sigmoid
+pub(crate) fn default_activation(index: usize, input: usize, hidden: usize) -> Activation {
+    if index < input {
+        Activation::Identity
+    } else if index < input + hidden {
+        Activation::Tanh
+    } else {
+        Activation::Sigmoid
+    }
 }
 
-fn sample_normal(std_dev: f32) -> f32 {
+fn sample_normal<R: Rng + ?Sized>(std_dev: f32, rng: &mut R) -> f32 {
     let normal = Normal::new(0.0, std_dev).expect("Invalid parameters for Normal distribution");
-    let mut rng = thread_rng();
-    normal.sample(&mut rng)
+    normal.sample(rng)
 }
\ No newline at end of file
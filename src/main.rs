@@ -1,4 +1,5 @@
 // BENCHMARKING EXAMPLE
+// cargo run --release -- --benchmark
 
 use minet_ai::*;
 
@@ -33,7 +34,7 @@ const TABLE_EXPECTED: &[&[f32]] = &[
 ];
 
 fn main () {
-    let mut test = minet::new(3, 5, 2);
+    let test = minet::new(3, 5, 2);
     
     let output = test.forward(vec![0.0, 1.0, 1.0]);
     println!("Output: {:?}", output);
@@ -41,7 +42,9 @@ fn main () {
     let activation_map = test.forward_display(vec![0.0, 1.0, 1.0]);
     println!("Activation Map: {:?}", activation_map);
     
-    //benchmark();
+    if std::env::args().any(|arg| arg == "--benchmark") {
+        benchmark();
+    }
    
     /*
    let minet = Minet::new(3, 5, 2);
@@ -51,45 +54,38 @@ fn main () {
 }
 
 fn benchmark() {
-
-    
     let mut generation_sum = 0;
     for iteration in 0..ITERATIONS {
-        let mut population = minet::initialize_population(POPULATION, 3, 5, 2);
+        let population = Population::new(POPULATION, 3, 5, 2, EvolutionConfig::default());
         print_header();
 
-        for generation in 1..=GENERATIONS {
-            evaluate_population_fitness(&mut population, TABLE_INPUTS, TABLE_EXPECTED);
-    
-            if let Some(best) = get_best_network(&population) {
-    
-                if best.fitness >= PERFECT_FITNESS_THRESHOLD {
-                    println!(
-                        "\nPerfect network found at generation {} with fitness {:.5}.",
-                        generation, best.fitness
-                    );
-                    print_generation(generation, best, TABLE_EXPECTED, TABLE_INPUTS);
-                    if iteration == ITERATIONS - 1 {
-                        best.dot_to_file("best_network.dot").expect("Failed to write DOT file.");
-                    }
-
-
-                    break;
-                }
+        let result = Evolver::new(population, |network: &mut minet| network_fitness(network, TABLE_INPUTS, TABLE_EXPECTED))
+            .breeding(Breeding::Truncation { survival_rate: SURVIVAL_RATE })
+            .target_fitness(PERFECT_FITNESS_THRESHOLD)
+            .max_generations(GENERATIONS)
+            .run();
+
+        let best = &result.champion;
+        if result.stats.stop_reason == StopReason::TargetReached {
+            println!(
+                "\nPerfect network found at generation {} with fitness {:.5}.",
+                result.stats.generations, best.fitness
+            );
+            print_generation(result.stats.generations, best, TABLE_EXPECTED, TABLE_INPUTS);
+            if iteration == ITERATIONS - 1 {
+                best.dot_to_file("best_network.dot").expect("Failed to write DOT file.");
             }
-            generation_sum += 1;
-            population = minet::crossbreed_population(population, SURVIVAL_RATE, POPULATION);
         }
+        generation_sum += result.stats.generations;
     }
     println!("!!!   Average Generations to Perfection:: {}", generation_sum / ITERATIONS);
 }
 
-/// Evaluates and assigns fitness scores to each network in the population.
-fn evaluate_population_fitness(population: &mut Vec<minet>, inputs: &[&[f32]], expected: &[&[f32]]) {
-    population.iter_mut().for_each(|network| {
-        let outputs: Vec<f32> = inputs.iter().map(|&input| network.forward(input.to_vec())[0]).collect();
-        network.fitness = calculate_fitness(&outputs, &extract_first_column(expected));
-    });
+/// Scores a network on the truth table
+fn network_fitness(network: &minet, inputs: &[&[f32]], expected: &[&[f32]]) -> f32 {
+    let samples: Vec<Vec<f32>> = inputs.iter().map(|&input| input.to_vec()).collect();
+    let outputs: Vec<f32> = network.forward_batch(&samples).iter().map(|output| output[0]).collect();
+    calculate_fitness(&outputs, &extract_first_column(expected))
 }
 
 /// Extracts the first element from each expected output pair.
@@ -97,11 +93,6 @@ fn extract_first_column(data: &[&[f32]]) -> Vec<f32> {
     data.iter().map(|&pair| pair[0]).collect()
 }
 
-/// Retrieves the network with the highest fitness in the population.
-fn get_best_network(population: &[minet]) -> Option<&minet> {
-    population.iter().max_by(|a, b| a.fitness.partial_cmp(&b.fitness).unwrap())
-}
-
 /// Prints the table header.
 fn print_header() {
     println!(
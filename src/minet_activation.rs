@@ -1,3 +1,5 @@
+use std::fmt;
+
 #[inline]
 pub fn relu(x: f32) -> f32 {
     if x > 0.0 { x } else { 0.0 }
@@ -19,4 +21,114 @@ pub fn sigmoid(x: f32) -> f32 {
 
 pub fn tanh(x: f32) -> f32 {
     x.tanh()
-}
\ No newline at end of file
+}
+
+pub fn sine(x: f32) -> f32 {
+    x.sin()
+}
+
+pub fn gaussian(x: f32) -> f32 {
+    (-x * x).exp()
+}
+
+pub fn step(x: f32) -> f32 {
+    if x > 0.0 { 1.0 } else { 0.0 }
+}
+
+/// Activation function carried by every gene.
+/// Input neurons always pass their value through unchanged, whatever their gene holds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Activation {
+    Tanh,
+    Sigmoid,
+    Relu,
+    LeakyRelu,
+    Identity,
+    Sine,
+    Gaussian,
+    Step,
+    Abs,
+}
+
+impl Activation {
+    pub const ALL: [Activation; 9] = [
+        Activation::Tanh,
+        Activation::Sigmoid,
+        Activation::Relu,
+        Activation::LeakyRelu,
+        Activation::Identity,
+        Activation::Sine,
+        Activation::Gaussian,
+        Activation::Step,
+        Activation::Abs,
+    ];
+
+    #[inline]
+    pub fn apply(self, x: f32) -> f32 {
+        match self {
+            Activation::Tanh => tanh(x),
+            Activation::Sigmoid => sigmoid(x),
+            Activation::Relu => relu(x),
+            Activation::LeakyRelu => leaky_relu_default(x),
+            Activation::Identity => x,
+            Activation::Sine => sine(x),
+            Activation::Gaussian => gaussian(x),
+            Activation::Step => step(x),
+            Activation::Abs => x.abs(),
+        }
+    }
+
+    /// Derivative of the activation at pre-activation `x`, where `y` is `apply(x)`.
+    /// Step uses 0 everywhere. At 0, Relu and LeakyRelu take their left slope and Abs its right one.
+    #[inline]
+    pub fn derivative(self, x: f32, y: f32) -> f32 {
+        match self {
+            Activation::Tanh => 1.0 - y * y,
+            Activation::Sigmoid => y * (1.0 - y),
+            Activation::Relu => step(x),
+            Activation::LeakyRelu => if x > 0.0 { 1.0 } else { 0.01 },
+            Activation::Identity => 1.0,
+            Activation::Sine => x.cos(),
+            Activation::Gaussian => -2.0 * x * y,
+            Activation::Step => 0.0,
+            Activation::Abs => if x < 0.0 { -1.0 } else { 1.0 },
+        }
+    }
+
+    /// Stable single byte id, used by the genome encoding
+    pub fn to_byte(self) -> u8 {
+        match self {
+            Activation::Tanh => 0,
+            Activation::Sigmoid => 1,
+            Activation::Relu => 2,
+            Activation::LeakyRelu => 3,
+            Activation::Identity => 4,
+            Activation::Sine => 5,
+            Activation::Gaussian => 6,
+            Activation::Step => 7,
+            Activation::Abs => 8,
+        }
+    }
+
+    pub fn from_byte(byte: u8) -> Option<Activation> {
+        Activation::ALL.get(byte as usize).copied()
+    }
+}
+
+impl fmt::Display for Activation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Activation::Tanh => "tanh",
+            Activation::Sigmoid => "sigmoid",
+            Activation::Relu => "relu",
+            Activation::LeakyRelu => "leaky_relu",
+            Activation::Identity => "identity",
+            Activation::Sine => "sine",
+            Activation::Gaussian => "gaussian",
+            Activation::Step => "step",
+            Activation::Abs => "abs",
+        };
+        write!(f, "{}", name)
+    }
+}
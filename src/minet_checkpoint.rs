@@ -0,0 +1,364 @@
+use crate::{minet, DecodeError, EvolutionConfig, Population, SpeciationConfig, Species};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::fmt::{self, Write as _};
+use std::fs;
+use std::io::{self, Write as _};
+use std::path::Path;
+
+// Checkpoint format, one record per line:
+//   "MINET CHECKPOINT 1"
+//   generation, since_improvement, next_species_id: "<name> <usize>"
+//   "rng <seed hex> <stream> <word position>"
+//   "evolution <field> <value>" and "speciation <field> <value>" per config field,
+//     floats stored as the hex of their bits so they round-trip exactly
+//   "member <fitness bits> <encoded genome>" per member
+//   "fame <fitness bits> <encoded genome>" per hall of fame entry
+//   "species <id> <best fitness bits> <last improved> <member indices, comma separated or -> <encoded representative>"
+// Config fields missing from the file keep their default, so older checkpoints still load.
+const HEADER: &str = "MINET CHECKPOINT 1";
+
+#[derive(Debug)]
+pub enum CheckpointError {
+    Io(io::Error),
+    /// A stored genome failed to decode
+    Decode(DecodeError),
+    /// A line could not be parsed, with its 1-based line number
+    Format { line: usize, message: String },
+}
+
+impl fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckpointError::Io(err) => write!(f, "checkpoint io error: {}", err),
+            CheckpointError::Decode(err) => write!(f, "checkpoint genome error: {}", err),
+            CheckpointError::Format { line, message } => write!(f, "checkpoint line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for CheckpointError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CheckpointError::Io(err) => Some(err),
+            CheckpointError::Decode(err) => Some(err),
+            CheckpointError::Format { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for CheckpointError {
+    fn from(err: io::Error) -> Self {
+        CheckpointError::Io(err)
+    }
+}
+
+impl From<DecodeError> for CheckpointError {
+    fn from(err: DecodeError) -> Self {
+        CheckpointError::Decode(err)
+    }
+}
+
+/// Everything needed to continue an Evolver run exactly where it stopped
+pub(crate) struct Checkpoint {
+    pub population: Population,
+    pub hall_of_fame: Vec<minet>,
+    pub since_improvement: usize,
+    pub rng: ChaCha8Rng,
+}
+
+impl Checkpoint {
+    /// Writes to a temporary file next to `path` and renames it over `path`
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut temporary = path.as_os_str().to_owned();
+        temporary.push(".tmp");
+
+        let mut file = fs::File::create(&temporary)?;
+        file.write_all(self.to_text().as_bytes())?;
+        file.sync_all()?;
+        drop(file);
+        fs::rename(&temporary, path)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Checkpoint, CheckpointError> {
+        Checkpoint::from_text(&fs::read_to_string(path)?)
+    }
+
+    fn to_text(&self) -> String {
+        let population = &self.population;
+        let mut out = String::new();
+        // Writing to a String cannot fail
+        let _ = writeln!(out, "{}", HEADER);
+        let _ = writeln!(out, "generation {}", population.generation);
+        let _ = writeln!(out, "since_improvement {}", self.since_improvement);
+        let _ = writeln!(out, "next_species_id {}", population.next_species_id);
+
+        let seed: String = self.rng.get_seed().iter().map(|byte| format!("{:02x}", byte)).collect();
+        let _ = writeln!(out, "rng {} {} {}", seed, self.rng.get_stream(), self.rng.get_word_pos());
+
+        write_evolution_config(&mut out, &population.config);
+        write_speciation_config(&mut out, &population.speciation);
+
+        for network in &population.members {
+            let _ = writeln!(out, "member {:08x} {}", network.fitness.to_bits(), network.encode());
+        }
+        for network in &self.hall_of_fame {
+            let _ = writeln!(out, "fame {:08x} {}", network.fitness.to_bits(), network.encode());
+        }
+        for species in &population.species {
+            let members = if species.members.is_empty() {
+                "-".to_string()
+            } else {
+                species.members.iter().map(|i| i.to_string()).collect::<Vec<_>>().join(",")
+            };
+            let _ = writeln!(
+                out,
+                "species {} {:08x} {} {} {}",
+                species.id,
+                species.best_fitness.to_bits(),
+                species.last_improved,
+                members,
+                species.representative.encode()
+            );
+        }
+        out
+    }
+
+    fn from_text(text: &str) -> Result<Checkpoint, CheckpointError> {
+        let mut lines = text.lines().enumerate().map(|(i, line)| (i + 1, line));
+        match lines.next() {
+            Some((_, HEADER)) => {}
+            Some((_, line)) if line.starts_with("MINET CHECKPOINT ") => {
+                return Err(format_error(1, format!("unsupported checkpoint version in {:?}", line)));
+            }
+            _ => return Err(format_error(1, "not a minet checkpoint")),
+        }
+
+        let mut config = EvolutionConfig::default();
+        let mut speciation = SpeciationConfig::default();
+        let mut generation = 0;
+        let mut since_improvement = 0;
+        let mut next_species_id = 0;
+        let mut rng = None;
+        let mut members = Vec::new();
+        let mut hall_of_fame = Vec::new();
+        let mut species = Vec::new();
+
+        for (number, line) in lines {
+            let mut fields = line.split_whitespace();
+            let Some(kind) = fields.next() else { continue };
+            let mut next = || fields.next().ok_or_else(|| format_error(number, "missing field"));
+            match kind {
+                "generation" => generation = parse(number, next()?)?,
+                "since_improvement" => since_improvement = parse(number, next()?)?,
+                "next_species_id" => next_species_id = parse(number, next()?)?,
+                "rng" => {
+                    let seed = parse_seed(number, next()?)?;
+                    let stream: u64 = parse(number, next()?)?;
+                    let word_pos: u128 = parse(number, next()?)?;
+                    let mut restored = ChaCha8Rng::from_seed(seed);
+                    restored.set_stream(stream);
+                    restored.set_word_pos(word_pos);
+                    rng = Some(restored);
+                }
+                "evolution" => {
+                    let (name, value) = (next()?, next()?);
+                    read_evolution_config(&mut config, name, value).map_err(|message| format_error(number, message))?;
+                }
+                "speciation" => {
+                    let (name, value) = (next()?, next()?);
+                    read_speciation_config(&mut speciation, name, value)
+                        .map_err(|message| format_error(number, message))?;
+                }
+                "member" | "fame" => {
+                    let fitness = parse_bits32(number, next()?)?;
+                    let mut network = minet::decode(next()?)?;
+                    network.fitness = fitness;
+                    if kind == "member" {
+                        members.push(network);
+                    } else {
+                        hall_of_fame.push(network);
+                    }
+                }
+                "species" => {
+                    let id = parse(number, next()?)?;
+                    let best_fitness = parse_bits32(number, next()?)?;
+                    let last_improved = parse(number, next()?)?;
+                    let indices = match next()? {
+                        "-" => Vec::new(),
+                        list => list.split(',').map(|i| parse(number, i)).collect::<Result<Vec<usize>, _>>()?,
+                    };
+                    let representative = minet::decode(next()?)?;
+                    let mut restored = Species::new(id, representative, last_improved);
+                    restored.best_fitness = best_fitness;
+                    restored.members = indices;
+                    species.push(restored);
+                }
+                other => return Err(format_error(number, format!("unknown record {:?}", other))),
+            }
+        }
+
+        let rng = rng.ok_or_else(|| format_error(0, "missing rng record"))?;
+        if members.is_empty() {
+            return Err(format_error(0, "no member records"));
+        }
+        let mut population = Population::from_members(members, config).with_speciation(speciation);
+        population.generation = generation;
+        population.species = species;
+        population.next_species_id = next_species_id;
+
+        Ok(Checkpoint { population, hall_of_fame, since_improvement, rng })
+    }
+}
+
+fn format_error(line: usize, message: impl Into<String>) -> CheckpointError {
+    CheckpointError::Format { line, message: message.into() }
+}
+
+fn parse<T: std::str::FromStr>(line: usize, value: &str) -> Result<T, CheckpointError> {
+    value.parse().map_err(|_| format_error(line, format!("invalid number {:?}", value)))
+}
+
+fn parse_bits32(line: usize, value: &str) -> Result<f32, CheckpointError> {
+    u32::from_str_radix(value, 16)
+        .map(f32::from_bits)
+        .map_err(|_| format_error(line, format!("invalid float bits {:?}", value)))
+}
+
+fn parse_seed(line: usize, value: &str) -> Result<[u8; 32], CheckpointError> {
+    let mut seed = [0u8; 32];
+    if value.len() != 64 || !value.is_ascii() {
+        return Err(format_error(line, "rng seed must be 64 hex digits"));
+    }
+    for (i, byte) in seed.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&value[i * 2..i * 2 + 2], 16)
+            .map_err(|_| format_error(line, "rng seed must be 64 hex digits"))?;
+    }
+    Ok(seed)
+}
+
+/// A config field value that round-trips exactly through text
+trait ConfigValue: Sized {
+    fn to_text(&self) -> String;
+    fn from_text(text: &str) -> Option<Self>;
+}
+
+impl ConfigValue for f32 {
+    fn to_text(&self) -> String {
+        format!("{:08x}", self.to_bits())
+    }
+
+    fn from_text(text: &str) -> Option<Self> {
+        u32::from_str_radix(text, 16).ok().map(f32::from_bits)
+    }
+}
+
+impl ConfigValue for f64 {
+    fn to_text(&self) -> String {
+        format!("{:016x}", self.to_bits())
+    }
+
+    fn from_text(text: &str) -> Option<Self> {
+        u64::from_str_radix(text, 16).ok().map(f64::from_bits)
+    }
+}
+
+impl ConfigValue for usize {
+    fn to_text(&self) -> String {
+        self.to_string()
+    }
+
+    fn from_text(text: &str) -> Option<Self> {
+        text.parse().ok()
+    }
+}
+
+impl ConfigValue for bool {
+    fn to_text(&self) -> String {
+        self.to_string()
+    }
+
+    fn from_text(text: &str) -> Option<Self> {
+        text.parse().ok()
+    }
+}
+
+impl ConfigValue for Option<usize> {
+    fn to_text(&self) -> String {
+        match self {
+            Some(value) => value.to_string(),
+            None => "none".to_string(),
+        }
+    }
+
+    fn from_text(text: &str) -> Option<Self> {
+        match text {
+            "none" => Some(None),
+            _ => text.parse().ok().map(Some),
+        }
+    }
+}
+
+/// Generates the writer and reader for every field of a config struct.
+/// The writer destructures the struct without `..`, so a field missing from the list fails to compile.
+macro_rules! config_fields {
+    ($write:ident, $read:ident, $config:ident, $prefix:literal, [$($field:ident),* $(,)?]) => {
+        fn $write(out: &mut String, config: &$config) {
+            let $config { $($field),* } = config;
+            $(let _ = writeln!(out, "{} {} {}", $prefix, stringify!($field), $field.to_text());)*
+        }
+
+        fn $read(config: &mut $config, name: &str, value: &str) -> Result<(), String> {
+            match name {
+                $(stringify!($field) => {
+                    config.$field = ConfigValue::from_text(value)
+                        .ok_or_else(|| format!("invalid value {:?} for {}", value, name))?;
+                })*
+                _ => return Err(format!("unknown {} field {:?}", $prefix, name)),
+            }
+            Ok(())
+        }
+    };
+}
+
+config_fields!(write_evolution_config, read_evolution_config, EvolutionConfig, "evolution", [
+    initial_weight_std_dev,
+    weight_std_dev,
+    bias_std_dev,
+    weight_mutation_probability,
+    weight_replace_probability,
+    synapse_swap_probability,
+    add_synapse_probability,
+    remove_synapse_probability,
+    add_recurrent_synapse_probability,
+    allow_self_loops,
+    add_neuron_probability,
+    remove_neuron_probability,
+    max_hidden,
+    activation_mutation_probability,
+    initial_synapses_per_neuron,
+    max_weight,
+    max_bias,
+    prefer_smaller_on_ties,
+    add_plasticity_probability,
+    plasticity_std_dev,
+    modulator_mutation_probability,
+    time_constant_std_dev,
+    min_time_constant,
+    max_time_constant,
+]);
+
+config_fields!(write_speciation_config, read_speciation_config, SpeciationConfig, "speciation", [
+    compatibility_threshold,
+    excess_coefficient,
+    disjoint_coefficient,
+    weight_coefficient,
+    bias_coefficient,
+    activation_coefficient,
+    target_species,
+    threshold_step,
+    stagnation_limit,
+    survival_rate,
+    species_elitism,
+]);
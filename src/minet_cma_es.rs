@@ -0,0 +1,350 @@
+use crate::minet;
+use rand::{thread_rng, Rng};
+use rand_distr::StandardNormal;
+
+/// Settings for `CmaEs`
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CmaEsConfig {
+    /// Initial step size, in parameter units
+    pub sigma: f64,
+    /// Candidates per generation, 4 + 3 ln(parameters) when None
+    pub population_size: Option<usize>,
+    /// Restarts allowed once a run converges or stalls, each with a larger population (IPOP)
+    pub max_restarts: usize,
+    /// Population size multiplier applied at every restart
+    pub population_growth: f64,
+    /// A run has converged once every coordinate's step falls below this
+    pub tolerance: f64,
+    /// A run has stalled once its best fitness has not improved for this many generations
+    pub stagnation_limit: usize,
+    /// Weights are clamped to [-max_weight, max_weight] in every candidate
+    pub max_weight: f32,
+    /// Biases are clamped to [-max_bias, max_bias] in every candidate
+    pub max_bias: f32,
+}
+
+impl Default for CmaEsConfig {
+    fn default() -> Self {
+        CmaEsConfig {
+            sigma: 0.3,
+            population_size: None,
+            max_restarts: 4,
+            population_growth: 2.0,
+            tolerance: 1e-8,
+            stagnation_limit: 200,
+            max_weight: 10.0,
+            max_bias: 10.0,
+        }
+    }
+}
+
+/// Covariance matrix adaptation evolution strategy over the weights and biases of one fixed topology.
+/// Works on `minet::flatten` vectors, which suits small networks: the covariance matrix has
+/// one entry per pair of parameters. Restarts begin again from the starting network with a larger population.
+pub struct CmaEs {
+    pub config: CmaEsConfig,
+    pub generation: usize,
+    pub restarts: usize,
+    /// Fittest candidate evaluated so far, with its fitness
+    best: minet,
+    /// Network the search started from, restarts begin here again
+    start: Vec<f64>,
+    limits: Vec<f64>,
+    run: Run,
+    converged: bool,
+}
+
+/// State of one CMA-ES run between restarts
+struct Run {
+    lambda: usize,
+    weights: Vec<f64>,
+    mu_eff: f64,
+    cc: f64,
+    cs: f64,
+    c1: f64,
+    c_mu: f64,
+    damps: f64,
+    chi_n: f64,
+    mean: Vec<f64>,
+    sigma: f64,
+    pc: Vec<f64>,
+    ps: Vec<f64>,
+    /// Covariance matrix, row-major
+    c: Vec<f64>,
+    /// Eigenvectors of `c` as columns, row-major
+    b: Vec<f64>,
+    /// Square roots of the eigenvalues of `c`
+    d: Vec<f64>,
+    generation: usize,
+    eigen_generation: usize,
+    best_fitness: f32,
+    last_improved: usize,
+}
+
+impl Run {
+    fn new(mean: Vec<f64>, sigma: f64, lambda: usize) -> Self {
+        let n = mean.len();
+        let nf = n as f64;
+        let mu = lambda / 2;
+        let raw: Vec<f64> = (0..mu).map(|i| (mu as f64 + 0.5).ln() - ((i + 1) as f64).ln()).collect();
+        let total: f64 = raw.iter().sum();
+        let weights: Vec<f64> = raw.iter().map(|w| w / total).collect();
+        let mu_eff = 1.0 / weights.iter().map(|w| w * w).sum::<f64>();
+
+        let cc = (4.0 + mu_eff / nf) / (nf + 4.0 + 2.0 * mu_eff / nf);
+        let cs = (mu_eff + 2.0) / (nf + mu_eff + 5.0);
+        let c1 = 2.0 / ((nf + 1.3).powi(2) + mu_eff);
+        let c_mu = (1.0 - c1).min(2.0 * (mu_eff - 2.0 + 1.0 / mu_eff) / ((nf + 2.0).powi(2) + mu_eff));
+        let damps = 1.0 + 2.0 * (((mu_eff - 1.0) / (nf + 1.0)).sqrt() - 1.0).max(0.0) + cs;
+        let chi_n = nf.sqrt() * (1.0 - 1.0 / (4.0 * nf) + 1.0 / (21.0 * nf * nf));
+
+        Run {
+            lambda,
+            weights,
+            mu_eff,
+            cc,
+            cs,
+            c1,
+            c_mu,
+            damps,
+            chi_n,
+            mean,
+            sigma,
+            pc: vec![0.0; n],
+            ps: vec![0.0; n],
+            c: identity(n),
+            b: identity(n),
+            d: vec![1.0; n],
+            generation: 0,
+            eigen_generation: 0,
+            best_fitness: f32::MIN,
+            last_improved: 0,
+        }
+    }
+}
+
+impl CmaEs {
+    pub fn new(network: minet, config: CmaEsConfig) -> Self {
+        let start: Vec<f64> = network.flatten().iter().map(|&p| p as f64).collect();
+        let limits = parameter_limits(&network, &config);
+        let lambda = config
+            .population_size
+            .unwrap_or_else(|| 4 + (3.0 * (start.len().max(1) as f64).ln()) as usize)
+            .max(4);
+        let run = Run::new(start.clone(), config.sigma, lambda);
+        let mut best = network;
+        best.fitness = f32::MIN;
+        CmaEs { config, generation: 0, restarts: 0, best, start, limits, run, converged: false }
+    }
+
+    /// Fittest network evaluated so far. Its fitness is f32::MIN until the first step.
+    pub fn best(&self) -> &minet {
+        &self.best
+    }
+
+    /// True once the last allowed run has converged or stalled, further steps do nothing
+    pub fn converged(&self) -> bool {
+        self.converged
+    }
+
+    /// Candidates evaluated per step in the current run
+    pub fn population_size(&self) -> usize {
+        self.run.lambda
+    }
+
+    /// Current step size
+    pub fn sigma(&self) -> f64 {
+        self.run.sigma
+    }
+
+    /// Samples and scores one generation, updates the distribution, and restarts if the run has ended.
+    /// Returns the best fitness of the generation, or of the whole search once it has converged.
+    pub fn step<F: FnMut(&minet) -> f32>(&mut self, fitness: F) -> f32 {
+        self.step_with_rng(fitness, &mut thread_rng())
+    }
+
+    pub fn step_with_rng<F: FnMut(&minet) -> f32, R: Rng + ?Sized>(&mut self, mut fitness: F, rng: &mut R) -> f32 {
+        if self.converged {
+            return self.best.fitness;
+        }
+        let n = self.start.len();
+        let run = &mut self.run;
+
+        // x = mean + sigma * B * (D . z), clamped to the parameter limits
+        let mut candidate = self.best.clone();
+        let mut samples: Vec<(f32, Vec<f64>)> = Vec::with_capacity(run.lambda);
+        for _ in 0..run.lambda {
+            let z: Vec<f64> = (0..n).map(|_| rng.sample(StandardNormal)).collect();
+            let x: Vec<f64> = (0..n)
+                .map(|row| {
+                    let y: f64 = (0..n).map(|k| run.b[row * n + k] * run.d[k] * z[k]).sum();
+                    (run.mean[row] + run.sigma * y).clamp(-self.limits[row], self.limits[row])
+                })
+                .collect();
+            let parameters: Vec<f32> = x.iter().map(|&v| v as f32).collect();
+            candidate.unflatten(&parameters);
+            let score = fitness(&candidate);
+            if score > self.best.fitness {
+                self.best = candidate.clone();
+                self.best.fitness = score;
+            }
+            samples.push((score, x));
+        }
+        samples.sort_by(|a, b| b.0.total_cmp(&a.0));
+        let generation_best = samples[0].0;
+
+        // Recombine the fittest half into the new mean
+        let old_mean = run.mean.clone();
+        for (i, mean) in run.mean.iter_mut().enumerate() {
+            *mean = run.weights.iter().zip(&samples).map(|(w, (_, x))| w * x[i]).sum();
+        }
+        let step: Vec<f64> = (0..n).map(|i| (run.mean[i] - old_mean[i]) / run.sigma).collect();
+
+        // Evolution paths, the conjugate one goes through C^(-1/2) = B D^-1 B^T
+        let mut whitened = vec![0.0; n];
+        for k in 0..n {
+            let projection: f64 = (0..n).map(|i| run.b[i * n + k] * step[i]).sum();
+            for (i, value) in whitened.iter_mut().enumerate() {
+                *value += run.b[i * n + k] * projection / run.d[k];
+            }
+        }
+        let cs_factor = (run.cs * (2.0 - run.cs) * run.mu_eff).sqrt();
+        for (p, w) in run.ps.iter_mut().zip(&whitened) {
+            *p = (1.0 - run.cs) * *p + cs_factor * w;
+        }
+        let ps_norm = run.ps.iter().map(|p| p * p).sum::<f64>().sqrt();
+        run.generation += 1;
+        let expected = (1.0 - (1.0 - run.cs).powi(2 * run.generation as i32)).sqrt() * run.chi_n;
+        let h_sigma = if ps_norm / expected < 1.4 + 2.0 / (n as f64 + 1.0) { 1.0 } else { 0.0 };
+        let cc_factor = (run.cc * (2.0 - run.cc) * run.mu_eff).sqrt();
+        for (p, s) in run.pc.iter_mut().zip(&step) {
+            *p = (1.0 - run.cc) * *p + h_sigma * cc_factor * s;
+        }
+
+        // Rank-one update from the path plus rank-mu update from the selected steps
+        let decay = 1.0 - run.c1 - run.c_mu + (1.0 - h_sigma) * run.c1 * run.cc * (2.0 - run.cc);
+        let selected: Vec<Vec<f64>> = samples
+            .iter()
+            .take(run.weights.len())
+            .map(|(_, x)| (0..n).map(|i| (x[i] - old_mean[i]) / run.sigma).collect())
+            .collect();
+        for i in 0..n {
+            for j in 0..=i {
+                let rank_mu: f64 = run.weights.iter().zip(&selected).map(|(w, y)| w * y[i] * y[j]).sum();
+                let value = decay * run.c[i * n + j] + run.c1 * run.pc[i] * run.pc[j] + run.c_mu * rank_mu;
+                run.c[i * n + j] = value;
+                run.c[j * n + i] = value;
+            }
+        }
+
+        run.sigma *= ((run.cs / run.damps) * (ps_norm / run.chi_n - 1.0)).exp();
+
+        // Decomposing C is O(n^3), so only redo it once C has drifted enough
+        let interval = (n as f64 / (10.0 * (run.c1 + run.c_mu))).max(1.0) as usize;
+        if run.generation - run.eigen_generation >= interval.min(n.max(1)) {
+            run.eigen_generation = run.generation;
+            let (values, vectors) = symmetric_eigen(&run.c, n);
+            run.b = vectors;
+            run.d = values.iter().map(|v| v.max(1e-20).sqrt()).collect();
+        }
+
+        if generation_best > run.best_fitness {
+            run.best_fitness = generation_best;
+            run.last_improved = run.generation;
+        }
+        self.generation += 1;
+
+        if self.run_ended() {
+            if self.restarts < self.config.max_restarts {
+                self.restarts += 1;
+                let lambda = (self.run.lambda as f64 * self.config.population_growth).ceil() as usize;
+                self.run = Run::new(self.start.clone(), self.config.sigma, lambda);
+            } else {
+                self.converged = true;
+            }
+        }
+        generation_best
+    }
+
+    /// A run ends when its steps shrink below the tolerance, the covariance becomes degenerate,
+    /// or its best fitness stalls
+    fn run_ended(&self) -> bool {
+        let run = &self.run;
+        let n = self.start.len();
+        let largest_step = (0..n).map(|i| run.sigma * run.c[i * n + i].sqrt()).fold(0.0, f64::max);
+        let (smallest, largest) = run.d.iter().fold((f64::MAX, 0.0f64), |(lo, hi), &d| (lo.min(d), hi.max(d)));
+        let condition = (largest / smallest).powi(2);
+        n == 0
+            || largest_step < self.config.tolerance
+            || !run.sigma.is_finite()
+            || condition > 1e14
+            || run.generation - run.last_improved > self.config.stagnation_limit
+    }
+}
+
+/// Clamp limit of every parameter, in `minet::flatten` order
+fn parameter_limits(network: &minet, config: &CmaEsConfig) -> Vec<f64> {
+    let mut limits = Vec::with_capacity(network.parameter_count());
+    for (i, gene) in network.genes.iter().enumerate() {
+        if i >= network.input {
+            limits.push(config.max_bias as f64);
+        }
+        limits.extend(gene.1.iter().map(|_| config.max_weight as f64));
+    }
+    limits
+}
+
+fn identity(n: usize) -> Vec<f64> {
+    let mut matrix = vec![0.0; n * n];
+    for i in 0..n {
+        matrix[i * n + i] = 1.0;
+    }
+    matrix
+}
+
+/// Eigen decomposition of a symmetric row-major matrix by cyclic Jacobi rotations.
+/// Returns the eigenvalues and the eigenvectors as columns of a row-major matrix.
+fn symmetric_eigen(matrix: &[f64], n: usize) -> (Vec<f64>, Vec<f64>) {
+    let mut a = matrix.to_vec();
+    let mut v = identity(n);
+    for _ in 0..64 {
+        let total: f64 = a.iter().map(|x| x * x).sum();
+        let diagonal: f64 = (0..n).map(|i| a[i * n + i] * a[i * n + i]).sum();
+        if total - diagonal <= 1e-24 * total {
+            break;
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                let apq = a[p * n + q];
+                if apq.abs() < 1e-300 {
+                    continue;
+                }
+                let theta = (a[q * n + q] - a[p * n + p]) / (2.0 * apq);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let t = if theta == 0.0 { 1.0 } else { t };
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for k in 0..n {
+                    let akp = a[k * n + p];
+                    let akq = a[k * n + q];
+                    a[k * n + p] = c * akp - s * akq;
+                    a[k * n + q] = s * akp + c * akq;
+                }
+                for k in 0..n {
+                    let apk = a[p * n + k];
+                    let aqk = a[q * n + k];
+                    a[p * n + k] = c * apk - s * aqk;
+                    a[q * n + k] = s * apk + c * aqk;
+                }
+                for k in 0..n {
+                    let vkp = v[k * n + p];
+                    let vkq = v[k * n + q];
+                    v[k * n + p] = c * vkp - s * vkq;
+                    v[k * n + q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+    ((0..n).map(|i| a[i * n + i]).collect(), v)
+}
@@ -0,0 +1,274 @@
+use crate::{minet, Activation};
+
+/// A frozen, flattened copy of a network for fast repeated inference.
+/// Built with `minet::compile`. Neurons that cannot influence any output are dropped, and every
+/// remaining neuron reads its incoming synapses from contiguous CSR arrays.
+/// `forward_into` allocates nothing and returns exactly the same values as `minet::forward`.
+#[derive(Clone, Debug)]
+pub struct CompiledMinet {
+    input: usize,
+    output: usize,
+    /// Per computed (hidden or output) neuron
+    bias: Vec<f32>,
+    activation: Vec<Activation>,
+    /// Incoming synapses of computed neuron k are `row_start[k]..row_start[k + 1]`,
+    /// the recurrent ones first, up to `forward_start[k]`
+    row_start: Vec<u32>,
+    forward_start: Vec<u32>,
+    /// Compiled index of each synapse's source neuron
+    sources: Vec<u32>,
+    weights: Vec<f32>,
+    /// Activations of the previous stateful pass, read by recurrent synapses
+    state: Vec<f32>,
+    /// Activations of the pass in progress
+    scratch: Vec<f32>,
+    /// Activations of a batched pass, neuron-major: neuron n's values for every sample are contiguous
+    batch_scratch: Vec<f32>,
+}
+
+impl minet {
+    /// Flattens the network for inference. The current recurrent state is carried over.
+    /// Plastic networks are frozen with the weights learned so far and stop learning.
+    pub fn compile(&self) -> CompiledMinet {
+        let length = self.genes.len();
+        let output_start = length - self.output;
+
+        // Walk backwards from the outputs to find every neuron that can influence them
+        let mut incoming: Vec<Vec<usize>> = vec![Vec::new(); length];
+        for (source, gene) in self.genes.iter().enumerate() {
+            for &(target, _) in &gene.1 {
+                incoming[target].push(source);
+            }
+        }
+        let mut kept = vec![false; length];
+        kept[..self.input].fill(true);
+        let mut stack: Vec<usize> = (output_start..length).collect();
+        while let Some(neuron) = stack.pop() {
+            if !kept[neuron] {
+                kept[neuron] = true;
+                stack.extend(incoming[neuron].iter().copied().filter(|&source| !kept[source]));
+            }
+        }
+
+        let mut compiled_index = vec![u32::MAX; length];
+        let mut count = 0;
+        for (i, _) in kept.iter().enumerate().filter(|(_, &keep)| keep) {
+            compiled_index[i] = count;
+            count += 1;
+        }
+
+        // Group synapses by target in the order `forward_display` accumulates them:
+        // recurrent ones by source first, then forward ones by source
+        let mut recurrent: Vec<Vec<(u32, f32)>> = vec![Vec::new(); length];
+        let mut forward: Vec<Vec<(u32, f32)>> = vec![Vec::new(); length];
+        for (source, &compiled_source) in compiled_index.iter().enumerate() {
+            for (target, weight) in self.lifetime_synapses(source) {
+                if !kept[target] {
+                    continue;
+                }
+                let synapse = (compiled_source, weight);
+                if target <= source {
+                    recurrent[target].push(synapse);
+                } else {
+                    forward[target].push(synapse);
+                }
+            }
+        }
+
+        let mut compiled = CompiledMinet {
+            input: self.input,
+            output: self.output,
+            bias: Vec::new(),
+            activation: Vec::new(),
+            row_start: vec![0],
+            forward_start: Vec::new(),
+            sources: Vec::new(),
+            weights: Vec::new(),
+            state: vec![0.0; count as usize],
+            scratch: vec![0.0; count as usize],
+            batch_scratch: Vec::new(),
+        };
+        for i in (self.input..length).filter(|&i| kept[i]) {
+            let (bias, _, activation) = self.genes[i];
+            compiled.bias.push(bias);
+            compiled.activation.push(activation);
+            for &(source, weight) in &recurrent[i] {
+                compiled.sources.push(source);
+                compiled.weights.push(weight);
+            }
+            compiled.forward_start.push(compiled.sources.len() as u32);
+            for &(source, weight) in &forward[i] {
+                compiled.sources.push(source);
+                compiled.weights.push(weight);
+            }
+            compiled.row_start.push(compiled.sources.len() as u32);
+        }
+        for (i, &value) in self.state.iter().enumerate().take(length) {
+            if kept[i] {
+                compiled.state[compiled_index[i] as usize] = value;
+            }
+        }
+        compiled
+    }
+
+    /// Runs one forward pass per sample, reading but not updating the recurrent state.
+    /// Compiles the network once and evaluates every sample together, which is much faster
+    /// than calling `forward` in a loop for more than a handful of samples.
+    pub fn forward_batch(&self, inputs: &[Vec<f32>]) -> Vec<Vec<f32>> {
+        let mut compiled = self.compile();
+        let mut flat_inputs = vec![0.0; inputs.len() * self.input];
+        for (row, sample) in flat_inputs.chunks_exact_mut(self.input.max(1)).zip(inputs) {
+            let count = row.len().min(sample.len());
+            row[..count].copy_from_slice(&sample[..count]);
+        }
+        let mut outputs = vec![0.0; inputs.len() * self.output];
+        compiled.forward_batch_into(&flat_inputs, &mut outputs);
+        outputs.chunks(self.output.max(1)).take(inputs.len()).map(|row| row.to_vec()).collect()
+    }
+
+    /// Runs every network in `population` on the same inputs and returns each one's outputs,
+    /// reusing a single activation buffer for the whole population
+    pub fn forward_population(population: &[minet], inputs: &[f32]) -> Vec<Vec<f32>> {
+        let mut activation_map = Vec::new();
+        population
+            .iter()
+            .map(|network| {
+                network.activate_into(inputs, &mut activation_map);
+                activation_map[network.input + network.hidden..].to_vec()
+            })
+            .collect()
+    }
+}
+
+impl CompiledMinet {
+    pub fn input_count(&self) -> usize {
+        self.input
+    }
+
+    pub fn output_count(&self) -> usize {
+        self.output
+    }
+
+    /// Neurons left after pruning, inputs included
+    pub fn neuron_count(&self) -> usize {
+        self.scratch.len()
+    }
+
+    pub fn synapse_count(&self) -> usize {
+        self.sources.len()
+    }
+
+    /// Runs a forward pass and writes the outputs into `outputs`, reading but not updating the recurrent state.
+    /// Missing inputs read as zero, like `minet::forward`.
+    ///
+    /// Panics if `outputs` does not hold exactly `output_count()` values.
+    pub fn forward_into(&mut self, inputs: &[f32], outputs: &mut [f32]) {
+        self.run(inputs);
+        outputs.copy_from_slice(&self.scratch[self.scratch.len() - self.output..]);
+    }
+
+    /// Same as `forward_into`, then stores every activation as the state for the next call,
+    /// like `minet::forward_stateful`
+    pub fn forward_stateful_into(&mut self, inputs: &[f32], outputs: &mut [f32]) {
+        self.forward_into(inputs, outputs);
+        std::mem::swap(&mut self.state, &mut self.scratch);
+    }
+
+    /// Clears the stored activations, as if no stateful forward pass had run yet
+    pub fn reset_state(&mut self) {
+        self.state.fill(0.0);
+    }
+
+    fn run(&mut self, inputs: &[f32]) {
+        let values = &mut self.scratch;
+        for (i, value) in values.iter_mut().enumerate().take(self.input) {
+            *value = inputs.get(i).copied().unwrap_or(0.0);
+        }
+
+        for k in 0..self.bias.len() {
+            let start = self.row_start[k] as usize;
+            let split = self.forward_start[k] as usize;
+            let end = self.row_start[k + 1] as usize;
+
+            let mut sum = 0.0;
+            for j in start..split {
+                sum += self.state[self.sources[j] as usize] * self.weights[j];
+            }
+            for j in split..end {
+                sum += values[self.sources[j] as usize] * self.weights[j];
+            }
+            values[self.input + k] = self.activation[k].apply(sum + self.bias[k]);
+        }
+    }
+
+    /// Runs one forward pass per sample, reading but not updating the recurrent state.
+    /// `inputs` holds the samples one after another, `input_count()` values each, and `outputs`
+    /// receives `output_count()` values per sample in the same order. Samples are evaluated together
+    /// in a structure-of-arrays buffer so the inner loops run over contiguous memory and vectorize.
+    /// Every value matches `forward_into` on the same sample exactly.
+    ///
+    /// Panics if the slice lengths do not describe the same number of samples.
+    pub fn forward_batch_into(&mut self, inputs: &[f32], outputs: &mut [f32]) {
+        if self.output == 0 {
+            return;
+        }
+        let batch = outputs.len() / self.output;
+        assert_eq!(outputs.len(), batch * self.output, "outputs must hold output_count() values per sample");
+        assert_eq!(inputs.len(), batch * self.input, "inputs must hold input_count() values per sample");
+
+        let values = &mut self.batch_scratch;
+        values.clear();
+        values.resize(self.scratch.len() * batch, 0.0);
+
+        for (sample, row) in inputs.chunks_exact(self.input.max(1)).enumerate().take(batch) {
+            for (i, &input) in row.iter().enumerate() {
+                values[i * batch + sample] = input;
+            }
+        }
+
+        for k in 0..self.bias.len() {
+            let start = self.row_start[k] as usize;
+            let split = self.forward_start[k] as usize;
+            let end = self.row_start[k + 1] as usize;
+
+            // Every source is an earlier neuron, so split the buffer to borrow them next to the target
+            let (earlier, rest) = values.split_at_mut((self.input + k) * batch);
+            let sums = &mut rest[..batch];
+            sums.fill(0.0);
+            for j in start..split {
+                let term = self.state[self.sources[j] as usize] * self.weights[j];
+                for sum in sums.iter_mut() {
+                    *sum += term;
+                }
+            }
+            for j in split..end {
+                let source = self.sources[j] as usize;
+                let weight = self.weights[j];
+                for (sum, &value) in sums.iter_mut().zip(&earlier[source * batch..(source + 1) * batch]) {
+                    *sum += value * weight;
+                }
+            }
+            let (bias, activation) = (self.bias[k], self.activation[k]);
+            for sum in sums.iter_mut() {
+                *sum = activation.apply(*sum + bias);
+            }
+        }
+
+        let output_start = self.scratch.len() - self.output;
+        for (sample, row) in outputs.chunks_exact_mut(self.output).enumerate() {
+            for (o, output) in row.iter_mut().enumerate() {
+                *output = values[(output_start + o) * batch + sample];
+            }
+        }
+    }
+
+    /// Runs every network on the same inputs with `forward_into`.
+    /// `outputs` receives each network's outputs one after another, so all networks must have the same output count.
+    pub fn forward_population_into(networks: &mut [CompiledMinet], inputs: &[f32], outputs: &mut [f32]) {
+        let mut rows = outputs.chunks_mut(networks.first().map_or(1, |n| n.output.max(1)));
+        for network in networks.iter_mut() {
+            let row = rows.next().expect("outputs must hold output_count() values per network");
+            network.forward_into(inputs, row);
+        }
+    }
+}
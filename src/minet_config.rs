@@ -0,0 +1,94 @@
+/// Tunable parameters for creating, mutating and crossbreeding networks.
+/// Override only what you need:
+/// `EvolutionConfig { weight_std_dev: 0.2, ..Default::default() }`
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EvolutionConfig {
+    /// Standard deviation of the weight given to a newly created synapse
+    pub initial_weight_std_dev: f32,
+    /// Standard deviation of the noise added to a weight when it mutates
+    pub weight_std_dev: f32,
+    /// Standard deviation of the noise added to every bias on mutation
+    pub bias_std_dev: f32,
+    /// Chance that each individual synapse has its weight mutated
+    pub weight_mutation_probability: f64,
+    /// Chance that a mutating weight is replaced by a fresh random weight instead of nudged
+    pub weight_replace_probability: f64,
+    /// Chance of removing the weakest synapse and adding a random one
+    pub synapse_swap_probability: f64,
+    /// Chance of adding one random synapse
+    pub add_synapse_probability: f64,
+    /// Chance of removing one random synapse
+    pub remove_synapse_probability: f64,
+    /// Chance of adding one recurrent synapse, which reads the previous tick's activation.
+    /// Recurrent networks should be driven with `forward_stateful`.
+    pub add_recurrent_synapse_probability: f64,
+    /// Whether recurrent synapses may connect a neuron to itself
+    pub allow_self_loops: bool,
+    /// Chance of splitting a random synapse with a new hidden neuron
+    pub add_neuron_probability: f64,
+    /// Chance of deleting a random hidden neuron that has no incoming or no outgoing synapses
+    pub remove_neuron_probability: f64,
+    /// Hidden neurons are never added beyond this count
+    pub max_hidden: usize,
+    /// Chance that each hidden or output neuron swaps its activation function
+    pub activation_mutation_probability: f64,
+    /// Random incoming synapses attempted for every hidden and output neuron in `minet::new`
+    pub initial_synapses_per_neuron: usize,
+    /// Weights are clamped to [-max_weight, max_weight]
+    pub max_weight: f32,
+    /// Biases are clamped to [-max_bias, max_bias]
+    pub max_bias: f32,
+    /// When fitness is tied, rank the network with the smaller `complexity` first in survivor, elite,
+    /// species and migration rankings, the hall of fame, MAP-Elites cells and `best`
+    pub prefer_smaller_on_ties: bool,
+    /// Chance that a network without plasticity evolves a random Hebbian rule, see `Plasticity`
+    pub add_plasticity_probability: f64,
+    /// Standard deviation of the noise added to every Hebbian rule coefficient on mutation
+    pub plasticity_std_dev: f32,
+    /// Chance that a plastic network moves its neuromodulator to a random neuron, or drops it
+    pub modulator_mutation_probability: f64,
+    /// Standard deviation of the log-normal noise that scales every time constant on mutation, see `Ctrnn`
+    pub time_constant_std_dev: f32,
+    /// Time constants are clamped to [min_time_constant, max_time_constant]
+    pub min_time_constant: f32,
+    pub max_time_constant: f32,
+}
+
+impl Default for EvolutionConfig {
+    fn default() -> Self {
+        EvolutionConfig {
+            initial_weight_std_dev: 0.5,
+            weight_std_dev: 0.1,
+            bias_std_dev: 0.016,
+            weight_mutation_probability: 1.0,
+            weight_replace_probability: 0.0,
+            synapse_swap_probability: 0.4,
+            add_synapse_probability: 0.0,
+            remove_synapse_probability: 0.0,
+            add_recurrent_synapse_probability: 0.0,
+            allow_self_loops: true,
+            add_neuron_probability: 0.0,
+            remove_neuron_probability: 0.0,
+            max_hidden: 64,
+            activation_mutation_probability: 0.02,
+            initial_synapses_per_neuron: 3,
+            max_weight: 10.0,
+            max_bias: 10.0,
+            prefer_smaller_on_ties: false,
+            add_plasticity_probability: 0.0,
+            plasticity_std_dev: 0.02,
+            modulator_mutation_probability: 0.02,
+            time_constant_std_dev: 0.1,
+            min_time_constant: 0.05,
+            max_time_constant: 10.0,
+        }
+    }
+}
+
+impl EvolutionConfig {
+    /// Clamps a weight into [-max_weight, max_weight]
+    pub(crate) fn clamp_weight(&self, weight: f32) -> f32 {
+        weight.clamp(-self.max_weight, self.max_weight)
+    }
+}
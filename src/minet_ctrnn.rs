@@ -0,0 +1,178 @@
+use crate::{minet, sample_normal, EvolutionConfig};
+use rand::{thread_rng, Rng};
+
+/// Numerical method used by `Ctrnn::step`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Integrator {
+    /// One derivative evaluation per step, stable while dt stays well below the smallest time constant
+    Euler,
+    /// Classic fourth order Runge-Kutta, four derivative evaluations per step
+    RungeKutta4,
+}
+
+/// Continuous-time recurrent network driven by a `minet` genome.
+/// Every hidden and output neuron i integrates `tau_i * dy_i/dt = -y_i + sum_j(weight_ji * rate_j)`
+/// over its incoming synapses, where `rate_j = activation_j(y_j + bias_j)` and input rates are the external inputs.
+/// Forward and recurrent synapses are treated alike. Time constants live in `minet::time_constants`,
+/// so they are mutated, crossbred and encoded with the rest of the genome. Hebbian plasticity does not run here.
+#[derive(Clone, Debug)]
+pub struct Ctrnn {
+    pub network: minet,
+    /// Simulated time advanced by every step
+    pub dt: f32,
+    pub integrator: Integrator,
+    /// Potential y of every neuron, inputs are unused
+    potentials: Vec<f32>,
+    /// Derivative of every stage, only the first is used by Euler
+    stages: [Vec<f32>; 4],
+    /// Potentials at an intermediate Runge-Kutta stage
+    trial: Vec<f32>,
+    rates: Vec<f32>,
+}
+
+impl Ctrnn {
+    /// Wraps a network, giving every neuron a time constant of 1.0 if it has none yet. Steps with Euler.
+    pub fn new(mut network: minet, dt: f32) -> Self {
+        if network.time_constants.len() != network.genes.len() {
+            network.time_constants.resize(network.genes.len(), 1.0);
+        }
+        let neurons = network.genes.len();
+        Ctrnn {
+            network,
+            dt,
+            integrator: Integrator::Euler,
+            potentials: vec![0.0; neurons],
+            stages: Default::default(),
+            trial: Vec::new(),
+            rates: Vec::new(),
+        }
+    }
+
+    pub fn with_integrator(mut self, integrator: Integrator) -> Self {
+        self.integrator = integrator;
+        self
+    }
+
+    /// Advances the network by `dt` with the inputs held constant, then returns the output rates
+    pub fn step(&mut self, inputs: Vec<f32>) -> Vec<f32> {
+        let neurons = self.network.genes.len();
+        // The network is public, so follow any layout change since the last step
+        self.potentials.resize(neurons, 0.0);
+        self.network.time_constants.resize(neurons, 1.0);
+        for stage in self.stages.iter_mut() {
+            stage.resize(neurons, 0.0);
+        }
+        self.trial.resize(neurons, 0.0);
+
+        let dt = self.dt;
+        let [k1, k2, k3, k4] = &mut self.stages;
+        derivative(&self.network, &inputs, &self.potentials, &mut self.rates, k1);
+        match self.integrator {
+            Integrator::Euler => {
+                for (y, k) in self.potentials.iter_mut().zip(k1.iter()) {
+                    *y += dt * k;
+                }
+            }
+            Integrator::RungeKutta4 => {
+                advance(&mut self.trial, &self.potentials, k1, 0.5 * dt);
+                derivative(&self.network, &inputs, &self.trial, &mut self.rates, k2);
+                advance(&mut self.trial, &self.potentials, k2, 0.5 * dt);
+                derivative(&self.network, &inputs, &self.trial, &mut self.rates, k3);
+                advance(&mut self.trial, &self.potentials, k3, dt);
+                derivative(&self.network, &inputs, &self.trial, &mut self.rates, k4);
+                for (i, y) in self.potentials.iter_mut().enumerate() {
+                    *y += dt / 6.0 * (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i]);
+                }
+            }
+        }
+
+        let output_start = neurons - self.network.output;
+        (output_start..neurons).map(|i| firing_rate(&self.network, i, self.potentials[i])).collect()
+    }
+
+    /// Potential of every neuron, inputs are always 0
+    pub fn potentials(&self) -> &[f32] {
+        &self.potentials
+    }
+
+    /// Sets every potential back to 0
+    pub fn reset(&mut self) {
+        self.potentials = vec![0.0; self.network.genes.len()];
+    }
+
+    /// Crossbreeds the genomes, time constants included. The child keeps this network's dt and integrator.
+    pub fn crossbreed(&self, other: &Ctrnn, config: &EvolutionConfig) -> Ctrnn {
+        self.crossbreed_with_rng(other, config, &mut thread_rng())
+    }
+
+    pub fn crossbreed_with_rng<R: Rng + ?Sized>(&self, other: &Ctrnn, config: &EvolutionConfig, rng: &mut R) -> Ctrnn {
+        let child = self.network.crossbreed_with_rng(&other.network, config, rng);
+        Ctrnn::new(child, self.dt).with_integrator(self.integrator)
+    }
+
+    /// DOT export of the genome, time constants included
+    pub fn to_dot(&self) -> String {
+        self.network.to_dot()
+    }
+
+    /// Prints the genome, time constants included
+    pub fn display(&self) {
+        self.network.display();
+    }
+}
+
+impl minet {
+    /// Gives every neuron the same time constant, turning the genome into one for `Ctrnn`
+    pub fn with_time_constants(mut self, tau: f32) -> Self {
+        self.time_constants = vec![tau; self.genes.len()];
+        self
+    }
+
+    /// Returns true if the genome carries time constants for `Ctrnn`
+    pub fn is_continuous(&self) -> bool {
+        !self.time_constants.is_empty()
+    }
+
+    /// Scales every time constant by log-normal noise, so they stay positive
+    pub(crate) fn mutate_time_constants<R: Rng + ?Sized>(&mut self, config: &EvolutionConfig, rng: &mut R) {
+        for tau in self.time_constants.iter_mut().skip(self.input) {
+            *tau *= sample_normal(config.time_constant_std_dev, rng).exp();
+            *tau = tau.clamp(config.min_time_constant, config.max_time_constant);
+        }
+    }
+}
+
+/// Sets `trial` to `start + step * slopes`
+fn advance(trial: &mut [f32], start: &[f32], slopes: &[f32], step: f32) {
+    for ((trial, y), k) in trial.iter_mut().zip(start).zip(slopes) {
+        *trial = y + step * k;
+    }
+}
+
+fn firing_rate(network: &minet, neuron: usize, potential: f32) -> f32 {
+    let (bias, _, activation) = &network.genes[neuron];
+    activation.apply(potential + bias)
+}
+
+/// Fills `slopes` with dy/dt of every neuron at the given potentials
+fn derivative(network: &minet, inputs: &[f32], potentials: &[f32], rates: &mut Vec<f32>, slopes: &mut [f32]) {
+    rates.clear();
+    rates.extend((0..network.genes.len()).map(|i| {
+        if i < network.input {
+            inputs.get(i).copied().unwrap_or(0.0)
+        } else {
+            firing_rate(network, i, potentials[i])
+        }
+    }));
+
+    slopes.fill(0.0);
+    for (source, gene) in network.genes.iter().enumerate() {
+        for &(target, weight) in &gene.1 {
+            slopes[target] += weight * rates[source];
+        }
+    }
+    for (i, slope) in slopes.iter_mut().enumerate() {
+        *slope = if i < network.input { 0.0 } else { (*slope - potentials[i]) / network.time_constants[i] };
+    }
+}
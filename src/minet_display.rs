@@ -5,25 +5,22 @@ impl minet {
         &self,
     ) {
         println!("===== GENOME =====");
-        let inputs = self.input as usize;
-        let outputs = self.output as usize;
+        let inputs = self.input;
+        let outputs = self.output;
         let length = self.genes.len();
         let hidden_space = length - outputs;
         let synapses_count = self.synapse_count();
         for (i, gene) in self.genes.iter().enumerate() {
-            let mut neuron_type: &str = "";
-            if i < inputs { 
-                neuron_type = "Input";
-            }
-            if i >= inputs && i < hidden_space {
-                neuron_type = "Hidden";
-            } 
-            if i >= hidden_space {
-                neuron_type = "Output";
-            }
+            let neuron_type = if i < inputs {
+                "Input"
+            } else if i < hidden_space {
+                "Hidden"
+            } else {
+                "Output"
+            };
             println!(
-                "{} | {} :: Bias: {}, Synapses: {:.2?}",
-                i , neuron_type, gene.0, gene.1
+                "{} | {} :: Bias: {}, Activation: {}, Synapses: {:.2?}{}",
+                i , neuron_type, gene.0, gene.2, gene.1, self.tau_label(i, ", Tau: ")
             );
 
         }
@@ -74,10 +71,10 @@ impl minet {
         dot.push_str("        labelloc=\"top\";\n");
         dot.push_str("        labeljust=\"center\";\n");
         for i in hidden_start..hidden_end {
-            let bias = self.genes[i].0;
+            let (bias, _, activation) = &self.genes[i];
             dot.push_str(&format!(
-                "        neuron_{} [shape=ellipse, style=filled, fillcolor=white, penwidth=1.5, label=\"H:{}\\nBias={:.2}\"];\n",
-                i, i, bias
+                "        neuron_{} [shape=ellipse, style=filled, fillcolor=white, penwidth=1.5, label=\"H:{}\\nBias={:.2}\\n{}{}\"];\n",
+                i, i, bias, activation, self.tau_label(i, "\\nTau=")
             ));
         }
         dot.push_str("    }\n\n");
@@ -92,10 +89,10 @@ impl minet {
         dot.push_str("        labeljust=\"center\";\n");
         dot.push_str("        rank=same;\n");
         for i in output_start..output_end {
-            let bias = self.genes[i].0;
+            let (bias, _, activation) = &self.genes[i];
             dot.push_str(&format!(
-                "        neuron_{} [shape=doublecircle, style=filled, fillcolor=white, penwidth=1.5, label=\"Out:{}\\nBias={:.2}\"];\n",
-                i, i, bias
+                "        neuron_{} [shape=doublecircle, style=filled, fillcolor=white, penwidth=1.5, label=\"Out:{}\\nBias={:.2}\\n{}{}\"];\n",
+                i, i, bias, activation, self.tau_label(i, "\\nTau=")
             ));
         }
         dot.push_str("    }\n\n");
@@ -103,11 +100,13 @@ impl minet {
         // Edges
         dot.push_str("    // Edges\n");
         dot.push_str("    edge [color=\"#555555\", penwidth=1.2];\n");
-        for (src, (_, synapses)) in self.genes.iter().enumerate() {
+        for (src, (_, synapses, _)) in self.genes.iter().enumerate() {
             for &(tgt, weight) in synapses {
+                // Recurrent synapses are dashed and kept out of the left-to-right ranking
+                let style = if tgt <= src { ", style=dashed, constraint=false" } else { "" };
                 dot.push_str(&format!(
-                    "    neuron_{} -> neuron_{} [label=\"{:.2}\", fontcolor=\"#333333\"];\n",
-                    src, tgt, weight
+                    "    neuron_{} -> neuron_{} [label=\"{:.2}\", fontcolor=\"#333333\"{}];\n",
+                    src, tgt, weight, style
                 ));
             }
         }
@@ -124,19 +123,21 @@ impl minet {
         std::fs::write(filename, dot_representation)
     }
     
+    /// Time constant of a computed neuron after the given prefix, empty for ordinary networks
+    fn tau_label(&self, neuron: usize, prefix: &str) -> String {
+        match self.time_constants.get(neuron) {
+            Some(tau) if neuron >= self.input => format!("{}{:.2}", prefix, tau),
+            _ => String::new(),
+        }
+    }
+
     pub fn display_genome(
         &self,
     ) {
         let encoded = self.encode();
-        let piece_length = encoded.len() / self.hidden;
-        let mut pieces = Vec::new();
-        for i in 0..self.hidden {
-            let start = i * piece_length;
-            let end = start + piece_length;
-            pieces.push(&encoded[start..end]);
-        }
-        for i in 0..pieces.len() {
-            println!("{}",pieces[i]);
+        let piece_length = encoded.len().div_ceil(self.hidden.max(1)).max(1);
+        for piece in encoded.as_bytes().chunks(piece_length) {
+            println!("{}", String::from_utf8_lossy(piece));
         }
     }
 
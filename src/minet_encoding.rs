@@ -1,79 +1,530 @@
-use crate::minet;
+use crate::{default_activation, minet, Activation, Gene, HebbianRule, Plasticity};
 use base64::{engine::general_purpose, Engine as _};
-use std::io::{Cursor, Read};
+use std::fmt;
 
+// Format v1, before base64:
+//   magic "MNT", version byte
+//   varint input, hidden, output
+//   per neuron: f32 bias, activation byte, varint synapse count, then (varint target, f32 weight) per synapse
+//   u32 CRC-32 of everything before it
+// All fixed width numbers are little endian.
+//
+// Format v2 adds a flags byte between the last neuron and the checksum, then every flagged section in order:
+//   bit 0, plasticity: f32 learning rate, a, b, c, d, f32 max weight, varint modulator + 1 (0 for none)
+//   bit 1, time constants: f32 per neuron
+// Networks with neither are still written as v1, so older builds can read them.
+//
+// Format v0 has no header or layer sizes:
+//   u16 neuron count, per neuron: f32 bias, u8 synapse count, then (u8 target, f32 weight) per synapse
+const MAGIC: &[u8; 3] = b"MNT";
+const VERSION: u8 = 2;
+const PLAIN_VERSION: u8 = 1;
+const FLAG_PLASTICITY: u8 = 1;
+const FLAG_TIME_CONSTANTS: u8 = 2;
+
+#[derive(Debug)]
+pub enum DecodeError {
+    /// The string is not valid base64
+    Base64(base64::DecodeError),
+    /// The data ended in the middle of a field
+    UnexpectedEnd,
+    /// The header names a format version this build cannot read
+    UnsupportedVersion(u8),
+    /// The stored checksum does not match the data
+    ChecksumMismatch { stored: u32, computed: u32 },
+    /// A varint does not fit in a usize
+    VarintOverflow,
+    /// An activation byte does not name a known activation
+    InvalidActivation(u8),
+    /// A synapse points at a neuron that does not exist or breaks the index ordering
+    InvalidSynapse { source: usize, target: usize },
+    /// The plasticity modulator names a neuron that does not exist
+    InvalidModulator(usize),
+    /// The flags byte names sections this build cannot read
+    UnknownSections(u8),
+    /// The neuron count does not match the layer sizes
+    LayoutMismatch { expected: usize, found: usize },
+    /// v0 strings do not store layer sizes; import them into a network of the right shape with `import_encoded`
+    MissingLayout,
+    /// Bytes were left over after the last field
+    TrailingBytes,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Base64(err) => write!(f, "invalid base64: {}", err),
+            DecodeError::UnexpectedEnd => write!(f, "encoded genome ended unexpectedly"),
+            DecodeError::UnsupportedVersion(version) => write!(f, "unsupported genome format version {}", version),
+            DecodeError::ChecksumMismatch { stored, computed } => {
+                write!(f, "checksum mismatch: stored {:08x}, computed {:08x}", stored, computed)
+            }
+            DecodeError::VarintOverflow => write!(f, "varint does not fit in usize"),
+            DecodeError::InvalidActivation(byte) => write!(f, "unknown activation id {}", byte),
+            DecodeError::InvalidSynapse { source, target } => {
+                write!(f, "invalid synapse from neuron {} to neuron {}", source, target)
+            }
+            DecodeError::InvalidModulator(neuron) => write!(f, "plasticity modulator {} is not a neuron", neuron),
+            DecodeError::UnknownSections(flags) => write!(f, "unknown genome sections in flags {:08b}", flags),
+            DecodeError::LayoutMismatch { expected, found } => {
+                write!(f, "expected {} neurons, found {}", expected, found)
+            }
+            DecodeError::MissingLayout => {
+                write!(f, "v0 genomes have no layer sizes, use import_encoded on a network of the right shape")
+            }
+            DecodeError::TrailingBytes => write!(f, "unexpected bytes after the genome"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DecodeError::Base64(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<base64::DecodeError> for DecodeError {
+    fn from(err: base64::DecodeError) -> Self {
+        DecodeError::Base64(err)
+    }
+}
 
 impl minet {
+    /// Encodes the genome and layer sizes as a base64 string in the current format
     pub fn encode(&self) -> String {
-    let mut bytes = Vec::new();
-
-    // Serialize the number of genes as u16
-    let num_genes = self.genes.len() as u16;
-    bytes.extend_from_slice(&num_genes.to_le_bytes());
-    
-    for (gene_val, connections) in &self.genes {
-        // Serialize the f32 gene value
-        bytes.extend_from_slice(&gene_val.to_le_bytes());
-
-        // Serialize the number of connections as u8
-        let num_connections = connections.len() as u8;
-        bytes.push(num_connections);
-
-        for (index, conn_val) in connections {
-            // Serialize the index as u8
-            bytes.push(*index as u8);
-
-            // Serialize the f32 connection value
-            bytes.extend_from_slice(&conn_val.to_le_bytes());
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MAGIC);
+        let mut flags = 0;
+        if self.plasticity.is_some() {
+            flags |= FLAG_PLASTICITY;
+        }
+        if self.is_continuous() {
+            flags |= FLAG_TIME_CONSTANTS;
+        }
+        bytes.push(if flags == 0 { PLAIN_VERSION } else { VERSION });
+
+        write_varint(&mut bytes, self.input);
+        write_varint(&mut bytes, self.hidden);
+        write_varint(&mut bytes, self.output);
+
+        for (bias, synapses, activation) in &self.genes {
+            bytes.extend_from_slice(&bias.to_le_bytes());
+            bytes.push(activation.to_byte());
+            write_varint(&mut bytes, synapses.len());
+            for (target, weight) in synapses {
+                write_varint(&mut bytes, *target);
+                bytes.extend_from_slice(&weight.to_le_bytes());
+            }
+        }
+
+        if flags != 0 {
+            bytes.push(flags);
+        }
+        if let Some(plasticity) = &self.plasticity {
+            let rule = plasticity.rule;
+            for value in [rule.learning_rate, rule.a, rule.b, rule.c, rule.d, plasticity.max_weight] {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+            write_varint(&mut bytes, plasticity.modulator.map_or(0, |neuron| neuron + 1));
+        }
+        if self.is_continuous() {
+            for i in 0..self.genes.len() {
+                let tau = self.time_constants.get(i).copied().unwrap_or(1.0);
+                bytes.extend_from_slice(&tau.to_le_bytes());
+            }
+        }
+
+        let checksum = crc32(&bytes);
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+
+        general_purpose::STANDARD_NO_PAD.encode(&bytes)
+    }
+
+    /// Builds a network from a string written by `encode`.
+    /// Strings in the old v0 format carry no layer sizes and return `DecodeError::MissingLayout`.
+    pub fn decode(encoded: &str) -> Result<minet, DecodeError> {
+        let bytes = general_purpose::STANDARD_NO_PAD.decode(encoded.trim())?;
+        if bytes.len() < MAGIC.len() {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+        if !bytes.starts_with(MAGIC) {
+            return Err(DecodeError::MissingLayout);
+        }
+        decode_current(&bytes)
+    }
+
+    /// Replaces this network's genome with an encoded one.
+    /// Current strings also set the layer sizes. v0 strings are read with this network's layer sizes,
+    /// and must contain the matching number of neurons.
+    pub fn import_encoded(&mut self, encoded: &str) -> Result<(), DecodeError> {
+        let bytes = general_purpose::STANDARD_NO_PAD.decode(encoded.trim())?;
+        let decoded = if bytes.starts_with(MAGIC) {
+            decode_current(&bytes)?
+        } else {
+            decode_v0(&bytes, self.input, self.hidden, self.output)?
+        };
+        self.genes = decoded.genes;
+        self.input = decoded.input;
+        self.hidden = decoded.hidden;
+        self.output = decoded.output;
+        self.plasticity = decoded.plasticity;
+        self.time_constants = decoded.time_constants;
+        self.reset_state();
+        Ok(())
+    }
+}
+
+/// Reads v1 and v2, which only differ in the sections after the last neuron
+fn decode_current(bytes: &[u8]) -> Result<minet, DecodeError> {
+    if bytes.len() < MAGIC.len() + 1 + 4 {
+        return Err(DecodeError::UnexpectedEnd);
+    }
+    let version = bytes[MAGIC.len()];
+    if version != VERSION && version != PLAIN_VERSION {
+        return Err(DecodeError::UnsupportedVersion(version));
+    }
+
+    let (body, stored) = bytes.split_at(bytes.len() - 4);
+    let stored = u32::from_le_bytes(stored.try_into().unwrap());
+    let computed = crc32(body);
+    if stored != computed {
+        return Err(DecodeError::ChecksumMismatch { stored, computed });
+    }
+
+    let mut reader = Reader { bytes: body, position: MAGIC.len() + 1 };
+    let input = reader.varint()?;
+    let hidden = reader.varint()?;
+    let output = reader.varint()?;
+    let total = input
+        .checked_add(hidden)
+        .and_then(|sum| sum.checked_add(output))
+        .ok_or(DecodeError::VarintOverflow)?;
+
+    let mut genes: Vec<Gene> = Vec::new();
+    for _ in 0..total {
+        let bias = reader.f32()?;
+        let activation_byte = reader.u8()?;
+        let activation = Activation::from_byte(activation_byte).ok_or(DecodeError::InvalidActivation(activation_byte))?;
+        let count = reader.varint()?;
+        // Every synapse takes at least five bytes, so a huge count is caught before allocating
+        let mut synapses = Vec::with_capacity(count.min(reader.remaining() / 5));
+        for _ in 0..count {
+            let target = reader.varint()?;
+            let weight = reader.f32()?;
+            synapses.push((target, weight));
+        }
+        genes.push((bias, synapses, activation));
+    }
+
+    let flags = if version == VERSION { reader.u8()? } else { 0 };
+    if flags & !(FLAG_PLASTICITY | FLAG_TIME_CONSTANTS) != 0 {
+        return Err(DecodeError::UnknownSections(flags));
+    }
+
+    let plasticity = if flags & FLAG_PLASTICITY != 0 {
+        let rule = HebbianRule::new(reader.f32()?, reader.f32()?, reader.f32()?, reader.f32()?, reader.f32()?);
+        let max_weight = reader.f32()?;
+        let modulator = match reader.varint()? {
+            0 => None,
+            stored if stored - 1 < total => Some(stored - 1),
+            stored => return Err(DecodeError::InvalidModulator(stored - 1)),
+        };
+        Some(Plasticity { rule, modulator, max_weight })
+    } else {
+        None
+    };
+    let time_constants = if flags & FLAG_TIME_CONSTANTS != 0 {
+        (0..total).map(|_| reader.f32()).collect::<Result<Vec<f32>, DecodeError>>()?
+    } else {
+        Vec::new()
+    };
+    if reader.remaining() > 0 {
+        return Err(DecodeError::TrailingBytes);
+    }
+
+    let mut network = build(genes, input, hidden, output)?;
+    network.plasticity = plasticity;
+    network.time_constants = time_constants;
+    Ok(network)
+}
+
+fn decode_v0(bytes: &[u8], input: usize, hidden: usize, output: usize) -> Result<minet, DecodeError> {
+    let mut reader = Reader { bytes, position: 0 };
+    let total = reader.u16()? as usize;
+    if total != input + hidden + output {
+        return Err(DecodeError::LayoutMismatch { expected: input + hidden + output, found: total });
+    }
+
+    let mut genes = Vec::with_capacity(total);
+    for i in 0..total {
+        let bias = reader.f32()?;
+        let count = reader.u8()? as usize;
+        let mut synapses = Vec::with_capacity(count);
+        for _ in 0..count {
+            let target = reader.u8()? as usize;
+            let weight = reader.f32()?;
+            synapses.push((target, weight));
+        }
+        // v0 predates activation genes, every neuron gets its layer default
+        genes.push((bias, synapses, default_activation(i, input, hidden)));
+    }
+    if reader.remaining() > 0 {
+        return Err(DecodeError::TrailingBytes);
+    }
+
+    build(genes, input, hidden, output)
+}
+
+/// Checks every synapse against the index ordering before handing out the network
+fn build(genes: Vec<Gene>, input: usize, hidden: usize, output: usize) -> Result<minet, DecodeError> {
+    let total = genes.len();
+    for (source, gene) in genes.iter().enumerate() {
+        for &(target, _) in &gene.1 {
+            // Forward synapses go up the index order, recurrent ones link hidden or output neurons
+            let valid = target < total && target >= input && (target > source || source >= input);
+            if !valid {
+                return Err(DecodeError::InvalidSynapse { source, target });
             }
         }
-    // Encode the byte vector to a base64 string
-    general_purpose::STANDARD_NO_PAD.encode(&bytes)
-    }
-    
-    pub fn import_encoded(
-        &mut self,
-        encoded: &str,
-    ) {
-    // Decode the base64 string to bytes
-    let bytes = general_purpose::STANDARD_NO_PAD.decode(encoded).unwrap();
-    let mut cursor = Cursor::new(bytes);
-
-    // Deserialize the number of genes
-    let mut num_genes_bytes = [0u8; 2];
-    cursor.read_exact(&mut num_genes_bytes).unwrap();
-    let num_genes = u16::from_le_bytes(num_genes_bytes) as usize;
-
-    let mut genes = Vec::new();
-
-    for _ in 0..num_genes {
-        // Deserialize the f32 gene value
-        let mut gene_val_bytes = [0u8; 4];
-        cursor.read_exact(&mut gene_val_bytes).unwrap();
-        let gene_val = f32::from_le_bytes(gene_val_bytes);
-
-        // Deserialize the number of connections
-        let mut num_connections_bytes = [0u8; 1];
-        cursor.read_exact(&mut num_connections_bytes).unwrap();
-        let num_connections = num_connections_bytes[0] as usize;
-
-        let mut connections = Vec::new();
-        for _ in 0..num_connections {
-            // Deserialize the index as u8
-            let mut index_byte = [0u8; 1];
-            cursor.read_exact(&mut index_byte).unwrap();
-            let index = index_byte[0] as usize;
-
-            // Deserialize the f32 connection value
-            let mut conn_val_bytes = [0u8; 4];
-            cursor.read_exact(&mut conn_val_bytes).unwrap();
-            let conn_val = f32::from_le_bytes(conn_val_bytes);
-
-            connections.push((index, conn_val));
+    }
+
+    Ok(minet {
+        genes,
+        input,
+        hidden,
+        output,
+        fitness: 0.0,
+        objectives: Vec::new(),
+        behavior: Vec::new(),
+        state: vec![0.0; total],
+        plasticity: None,
+        weight_changes: Vec::new(),
+        time_constants: Vec::new(),
+    })
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, count: usize) -> Result<&[u8], DecodeError> {
+        let end = self.position.checked_add(count).ok_or(DecodeError::UnexpectedEnd)?;
+        let slice = self.bytes.get(self.position..end).ok_or(DecodeError::UnexpectedEnd)?;
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn remaining(&self) -> usize {
+        self.bytes.len().saturating_sub(self.position)
+    }
+
+    fn u8(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, DecodeError> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn f32(&mut self) -> Result<f32, DecodeError> {
+        Ok(f32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    /// Unsigned LEB128
+    fn varint(&mut self) -> Result<usize, DecodeError> {
+        let mut value: usize = 0;
+        let mut shift = 0;
+        loop {
+            let byte = self.u8()?;
+            let bits = (byte & 0x7f) as usize;
+            if shift >= usize::BITS || (bits << shift) >> shift != bits {
+                return Err(DecodeError::VarintOverflow);
             }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+            shift += 7;
+        }
+    }
+}
+
+/// Unsigned LEB128
+fn write_varint(bytes: &mut Vec<u8>, mut value: usize) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes.push(byte);
+            return;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+/// CRC-32 (IEEE 802.3), bit by bit since genomes are small
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EvolutionConfig;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
 
-            genes.push((gene_val, connections));
+    fn network() -> minet {
+        let config = EvolutionConfig { add_recurrent_synapse_probability: 0.5, ..Default::default() };
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        let mut network = minet::new_with_rng(3, 5, 2, &config, &mut rng);
+        for _ in 0..10 {
+            network.mutate_with_rng(&config, &mut rng);
         }
-        self.genes = genes;
+        network.genes[4].2 = Activation::Sine;
+        network.genes[9].2 = Activation::Identity;
+        network
+    }
+
+    fn raw(encoded: &str) -> Vec<u8> {
+        general_purpose::STANDARD_NO_PAD.decode(encoded).unwrap()
+    }
+
+    /// Replaces the checksum, so tampered data gets past it
+    fn reseal(mut bytes: Vec<u8>) -> String {
+        bytes.truncate(bytes.len() - 4);
+        let checksum = crc32(&bytes);
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+        general_purpose::STANDARD_NO_PAD.encode(&bytes)
+    }
+
+    /// Hand-written v0 string: u16 neuron count, then per neuron f32 bias, u8 synapse count and (u8 target, f32 weight) pairs
+    fn v0(genes: &[(f32, Vec<(u8, f32)>)]) -> Vec<u8> {
+        let mut bytes = (genes.len() as u16).to_le_bytes().to_vec();
+        for (bias, synapses) in genes {
+            bytes.extend_from_slice(&bias.to_le_bytes());
+            bytes.push(synapses.len() as u8);
+            for (target, weight) in synapses {
+                bytes.push(*target);
+                bytes.extend_from_slice(&weight.to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    #[test]
+    fn crc32_matches_the_standard_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn plain_networks_round_trip_as_v1() {
+        let network = network();
+        assert!(network.genes.iter().enumerate().any(|(source, gene)| gene.1.iter().any(|&(target, _)| target <= source)));
+        let encoded = network.encode();
+        assert_eq!(raw(&encoded)[MAGIC.len()], PLAIN_VERSION);
+
+        let decoded = minet::decode(&encoded).unwrap();
+        assert_eq!((decoded.input, decoded.hidden, decoded.output), (3, 5, 2));
+        assert_eq!(decoded.genes, network.genes);
+        assert_eq!(decoded.encode(), encoded);
+    }
+
+    #[test]
+    fn plastic_and_continuous_networks_round_trip_as_v2() {
+        let mut network = network().with_time_constants(0.5);
+        network.time_constants[6] = 2.5;
+        network.plasticity = Some(Plasticity::new(HebbianRule::new(0.2, 0.5, -0.1, 0.3, 0.01)).with_modulator(1));
+        let encoded = network.encode();
+        let bytes = raw(&encoded);
+        assert_eq!(bytes[MAGIC.len()], VERSION);
+
+        let decoded = minet::decode(&encoded).unwrap();
+        assert_eq!(decoded.genes, network.genes);
+        assert_eq!(decoded.plasticity, network.plasticity);
+        assert_eq!(decoded.time_constants, network.time_constants);
+        assert_eq!(decoded.encode(), encoded);
+    }
+
+    #[test]
+    fn corrupted_strings_fail_the_checksum() {
+        let mut bytes = raw(&network().encode());
+        let middle = bytes.len() / 2;
+        bytes[middle] ^= 0x10;
+        let corrupted = general_purpose::STANDARD_NO_PAD.encode(&bytes);
+        assert!(matches!(minet::decode(&corrupted), Err(DecodeError::ChecksumMismatch { .. })));
+
+        let mut bytes = raw(&network().encode());
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0x01;
+        let corrupted = general_purpose::STANDARD_NO_PAD.encode(&bytes);
+        assert!(matches!(minet::decode(&corrupted), Err(DecodeError::ChecksumMismatch { .. })));
+    }
+
+    #[test]
+    fn malformed_strings_are_rejected() {
+        assert!(matches!(minet::decode("not base64!"), Err(DecodeError::Base64(_))));
+        assert!(matches!(minet::decode("TU5U"), Err(DecodeError::UnexpectedEnd)));
+
+        let mut bytes = raw(&network().encode());
+        bytes[MAGIC.len()] = 9;
+        assert!(matches!(minet::decode(&reseal(bytes)), Err(DecodeError::UnsupportedVersion(9))));
+
+        // A v2 genome whose flags byte names a section from the future
+        let mut network = network();
+        network.plasticity = Some(Plasticity::new(HebbianRule::hebbian(0.1)));
+        let mut bytes = raw(&network.encode());
+        let flags = bytes.len() - 4 - (6 * 4 + 1) - 1;
+        assert_eq!(bytes[flags], FLAG_PLASTICITY);
+        bytes[flags] |= 0x80;
+        assert!(matches!(minet::decode(&reseal(bytes)), Err(DecodeError::UnknownSections(_))));
+
+        let mut bytes = raw(&network.encode());
+        let checksum = bytes.split_off(bytes.len() - 4);
+        bytes.push(0);
+        bytes.extend_from_slice(&checksum);
+        assert!(matches!(minet::decode(&reseal(bytes)), Err(DecodeError::TrailingBytes)));
+    }
+
+    #[test]
+    fn v0_strings_import_with_default_activations() {
+        let genes = [(0.0, vec![(2, 0.5)]), (0.0, vec![(2, -1.5), (3, 0.25)]), (0.1, vec![(3, 2.0)]), (-0.2, vec![])];
+        let encoded = general_purpose::STANDARD_NO_PAD.encode(v0(&genes));
+        assert!(matches!(minet::decode(&encoded), Err(DecodeError::MissingLayout)));
+
+        let mut network = minet::new(2, 1, 1);
+        network.import_encoded(&encoded).unwrap();
+        assert_eq!(network.genes[1].1, vec![(2, -1.5), (3, 0.25)]);
+        assert_eq!(network.genes[2].0, 0.1);
+        let activations: Vec<Activation> = network.genes.iter().map(|gene| gene.2).collect();
+        assert_eq!(activations, [Activation::Identity, Activation::Identity, Activation::Tanh, Activation::Sigmoid]);
+
+        let mut wrong_shape = minet::new(2, 2, 1);
+        assert!(matches!(
+            wrong_shape.import_encoded(&encoded),
+            Err(DecodeError::LayoutMismatch { expected: 5, found: 4 })
+        ));
+    }
+
+    #[test]
+    fn v0_strings_with_trailing_bytes_are_rejected() {
+        let genes = [(0.0, vec![(2, 0.5)]), (0.0, vec![]), (0.1, vec![(3, 2.0)]), (-0.2, vec![])];
+        let mut bytes = v0(&genes);
+        bytes.extend_from_slice(&[0; 4]);
+        let encoded = general_purpose::STANDARD_NO_PAD.encode(bytes);
+        assert!(matches!(minet::new(2, 1, 1).import_encoded(&encoded), Err(DecodeError::TrailingBytes)));
     }
 }
@@ -0,0 +1,112 @@
+use crate::{minet, minet_training::OptimizerState, Optimizer};
+use rand::{thread_rng, Rng};
+use rand_distr::StandardNormal;
+
+/// Settings for `OpenAiEs`
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpenAiEsConfig {
+    /// Antithetic pairs sampled per step, each pair costs two evaluations
+    pub pairs: usize,
+    /// Standard deviation of the parameter noise
+    pub sigma: f32,
+    /// Applied to the estimated gradient, Adam as in the OpenAI-ES paper by default
+    pub optimizer: Optimizer,
+    /// L2 penalty pulling parameters towards zero
+    pub weight_decay: f32,
+    /// Weights are clamped to [-max_weight, max_weight] after every step
+    pub max_weight: f32,
+    /// Biases are clamped to [-max_bias, max_bias] after every step
+    pub max_bias: f32,
+}
+
+impl Default for OpenAiEsConfig {
+    fn default() -> Self {
+        OpenAiEsConfig {
+            pairs: 25,
+            sigma: 0.05,
+            optimizer: Optimizer::adam(0.01),
+            weight_decay: 0.005,
+            max_weight: 10.0,
+            max_bias: 10.0,
+        }
+    }
+}
+
+/// OpenAI-style natural evolution strategy over the weights and biases of one fixed topology.
+/// Each step evaluates antithetic Gaussian perturbations of the current network, turns their fitness
+/// into centered ranks, and moves the network along the rank-weighted noise.
+pub struct OpenAiEs {
+    /// The current search point, evaluate it to track progress. Its topology must not change between steps.
+    pub network: minet,
+    pub config: OpenAiEsConfig,
+    pub generation: usize,
+    optimizer: OptimizerState,
+}
+
+impl OpenAiEs {
+    pub fn new(network: minet, config: OpenAiEsConfig) -> Self {
+        let optimizer = OptimizerState::new(config.optimizer, network.parameter_count());
+        OpenAiEs { network, config, generation: 0, optimizer }
+    }
+
+    /// Runs one step and returns the mean fitness of the perturbed networks
+    pub fn step<F: FnMut(&minet) -> f32>(&mut self, fitness: F) -> f32 {
+        self.step_with_rng(fitness, &mut thread_rng())
+    }
+
+    pub fn step_with_rng<F: FnMut(&minet) -> f32, R: Rng + ?Sized>(&mut self, mut fitness: F, rng: &mut R) -> f32 {
+        let center = self.network.flatten();
+        let dimensions = center.len();
+        let pairs = self.config.pairs.max(1);
+        let sigma = self.config.sigma;
+
+        let mut candidate = self.network.clone();
+        let mut noise: Vec<Vec<f32>> = Vec::with_capacity(pairs);
+        // scores[2k] is center + sigma * noise[k], scores[2k + 1] is center - sigma * noise[k]
+        let mut scores = Vec::with_capacity(pairs * 2);
+        for _ in 0..pairs {
+            let epsilon: Vec<f32> = (0..dimensions).map(|_| rng.sample::<f32, _>(StandardNormal)).collect();
+            for sign in [1.0, -1.0] {
+                let perturbed: Vec<f32> = center.iter().zip(&epsilon).map(|(c, e)| c + sign * sigma * e).collect();
+                candidate.unflatten(&perturbed);
+                scores.push(fitness(&candidate));
+            }
+            noise.push(epsilon);
+        }
+
+        let ranks = centered_ranks(&scores);
+        let scale = 1.0 / (scores.len() as f32 * sigma);
+        let mut gradient = vec![0.0; dimensions];
+        for (k, epsilon) in noise.iter().enumerate() {
+            let weight = ranks[2 * k] - ranks[2 * k + 1];
+            for (g, e) in gradient.iter_mut().zip(epsilon) {
+                *g += weight * e;
+            }
+        }
+        // The optimizer descends, so step against the ascent direction plus the decay term
+        for (g, c) in gradient.iter_mut().zip(&center) {
+            *g = -*g * scale + self.config.weight_decay * c;
+        }
+
+        let mut parameters = center;
+        self.optimizer.step(&mut parameters, &gradient);
+        self.network.unflatten(&parameters);
+        self.network.clamp_parameters(self.config.max_weight, self.config.max_bias);
+        self.generation += 1;
+
+        scores.iter().sum::<f32>() / scores.len() as f32
+    }
+}
+
+/// Maps scores to their ranks spread evenly over [-0.5, 0.5], so the step ignores the fitness scale
+fn centered_ranks(scores: &[f32]) -> Vec<f32> {
+    let mut order: Vec<usize> = (0..scores.len()).collect();
+    order.sort_by(|&a, &b| scores[a].total_cmp(&scores[b]));
+    let mut ranks = vec![0.0; scores.len()];
+    let last = (scores.len().max(2) - 1) as f32;
+    for (rank, &i) in order.iter().enumerate() {
+        ranks[i] = rank as f32 / last - 0.5;
+    }
+    ranks
+}
@@ -0,0 +1,356 @@
+use crate::{
+    minet,
+    minet_parsimony::{rank_order, sort_best_first},
+    Checkpoint, CheckpointError, Parsimony, Population, Selection};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::cmp::Ordering;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Scores a network, higher is better.
+/// The network arrives with its state reset, so recurrent and plastic networks can be stepped
+/// with `forward_stateful` during evaluation without one evaluation leaking into the next.
+pub trait Fitness {
+    fn evaluate(&mut self, network: &mut minet) -> f32;
+}
+
+impl<F: FnMut(&mut minet) -> f32> Fitness for F {
+    fn evaluate(&mut self, network: &mut minet) -> f32 {
+        self(network)
+    }
+}
+
+/// How the Evolver builds each new generation
+pub enum Breeding {
+    /// `crossbreed_population`: the best `survival_rate` fraction survive and breed
+    Truncation { survival_rate: f32 },
+    /// `crossbreed_population_with_selection` with the given strategy and elite count
+    Selection { selection: Box<dyn Selection>, elitism: usize },
+    /// `Population::evolve`, using the population's speciation settings
+    Speciated,
+}
+
+/// Why a run ended
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StopReason {
+    TargetReached,
+    GenerationLimit,
+    TimeLimit,
+    Stagnated,
+    /// Writing a checkpoint failed, see `EvolutionResult::checkpoint_error`
+    CheckpointFailed,
+}
+
+/// Summary of one evaluated generation
+#[derive(Clone, Debug)]
+pub struct GenerationStats {
+    pub generation: usize,
+    pub best_fitness: f32,
+    pub mean_fitness: f32,
+    pub worst_fitness: f32,
+    pub mean_synapses: f32,
+    pub species: usize,
+    /// Time since the run started
+    pub elapsed: Duration,
+}
+
+/// Summary of a whole run
+#[derive(Clone, Debug)]
+pub struct RunStats {
+    /// Number of generations evaluated
+    pub generations: usize,
+    pub elapsed: Duration,
+    pub stop_reason: StopReason,
+    pub history: Vec<GenerationStats>,
+}
+
+pub struct EvolutionResult {
+    /// Fittest network evaluated during the run, with its fitness
+    pub champion: minet,
+    /// Fittest distinct networks evaluated during the run, best first
+    pub hall_of_fame: Vec<minet>,
+    pub stats: RunStats,
+    /// The population as it was when the run stopped
+    pub population: Population,
+    pub checkpoint_error: Option<io::Error>,
+}
+
+type Callback<'a> = Box<dyn FnMut(&GenerationStats, &Population) + 'a>;
+
+/// Runs the evaluate / check / breed loop until a stopping criterion is met.
+/// At least one generation is always evaluated, and without other limits a run stops after 1000 generations.
+pub struct Evolver<'a> {
+    pub population: Population,
+    pub breeding: Breeding,
+    fitness: Box<dyn Fitness + 'a>,
+    parsimony: Option<Parsimony>,
+    callbacks: Vec<Callback<'a>>,
+    target_fitness: Option<f32>,
+    max_generations: Option<usize>,
+    time_limit: Option<Duration>,
+    stagnation_limit: Option<usize>,
+    checkpoint: Option<(usize, PathBuf)>,
+    hall_of_fame_size: usize,
+    pub(crate) hall_of_fame: Vec<minet>,
+    pub(crate) since_improvement: usize,
+    pub(crate) rng: ChaCha8Rng,
+}
+
+const DEFAULT_MAX_GENERATIONS: usize = 1000;
+const DEFAULT_HALL_OF_FAME_SIZE: usize = 10;
+
+impl<'a> Evolver<'a> {
+    /// Panics if the population has no members
+    pub fn new(population: Population, fitness: impl Fitness + 'a) -> Self {
+        Evolver::from_boxed(population, Box::new(fitness))
+    }
+
+    /// Same as `new`, for a fitness that is already a trait object. Panics if the population has no members.
+    pub fn from_boxed(population: Population, fitness: Box<dyn Fitness + 'a>) -> Self {
+        assert!(!population.members.is_empty(), "an Evolver needs at least one network to evolve");
+        Evolver {
+            population,
+            breeding: Breeding::Truncation { survival_rate: 0.1 },
+            fitness,
+            parsimony: None,
+            callbacks: Vec::new(),
+            target_fitness: None,
+            max_generations: None,
+            time_limit: None,
+            stagnation_limit: None,
+            checkpoint: None,
+            hall_of_fame_size: DEFAULT_HALL_OF_FAME_SIZE,
+            hall_of_fame: Vec::new(),
+            since_improvement: 0,
+            rng: ChaCha8Rng::from_entropy(),
+        }
+    }
+
+    /// Continues a run from a checkpoint written by `checkpoint_every` or `save_checkpoint`.
+    /// Breeding, parsimony, stopping criteria and callbacks are not stored and must be set again.
+    /// With the same settings and a deterministic fitness the run continues exactly as if it had never stopped.
+    /// A checkpoint without members is a format error.
+    pub fn resume(path: impl AsRef<Path>, fitness: impl Fitness + 'a) -> Result<Self, CheckpointError> {
+        let checkpoint = Checkpoint::load(path)?;
+        let mut evolver = Evolver::new(checkpoint.population, fitness);
+        evolver.hall_of_fame = checkpoint.hall_of_fame;
+        evolver.since_improvement = checkpoint.since_improvement;
+        evolver.rng = checkpoint.rng;
+        Ok(evolver)
+    }
+
+    /// Makes the run reproducible
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.rng = ChaCha8Rng::seed_from_u64(seed);
+        self
+    }
+
+    pub fn breeding(mut self, breeding: Breeding) -> Self {
+        self.breeding = breeding;
+        self
+    }
+
+    /// Subtracts a size penalty from every evaluated fitness, so reported fitness, the target
+    /// and the hall of fame all see the penalized value
+    pub fn parsimony(mut self, parsimony: Parsimony) -> Self {
+        self.parsimony = Some(parsimony);
+        self
+    }
+
+    /// Stop once a network reaches this fitness
+    pub fn target_fitness(mut self, fitness: f32) -> Self {
+        self.target_fitness = Some(fitness);
+        self
+    }
+
+    /// Stop after evaluating this many generations, counted across resumed runs
+    pub fn max_generations(mut self, generations: usize) -> Self {
+        self.max_generations = Some(generations);
+        self
+    }
+
+    /// Stop once this much wall-clock time has passed, checked after every generation
+    pub fn time_limit(mut self, limit: Duration) -> Self {
+        self.time_limit = Some(limit);
+        self
+    }
+
+    /// Stop when the best fitness has not improved for this many generations
+    pub fn stagnation_limit(mut self, generations: usize) -> Self {
+        self.stagnation_limit = Some(generations);
+        self
+    }
+
+    /// Number of distinct top networks kept in the hall of fame
+    pub fn hall_of_fame_size(mut self, size: usize) -> Self {
+        self.hall_of_fame_size = size.max(1);
+        self
+    }
+
+    /// Writes a checkpoint to `path` after every `generations` generations are bred.
+    /// The file is replaced atomically, so a crash mid-write leaves the previous checkpoint intact.
+    pub fn checkpoint_every(mut self, generations: usize, path: impl Into<PathBuf>) -> Self {
+        self.checkpoint = Some((generations.max(1), path.into()));
+        self
+    }
+
+    /// Writes the population, hall of fame and RNG state so the run can be resumed with `Evolver::resume`
+    pub fn save_checkpoint(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        Checkpoint {
+            population: self.population.clone(),
+            hall_of_fame: self.hall_of_fame.clone(),
+            since_improvement: self.since_improvement,
+            rng: self.rng.clone(),
+        }
+        .save(path)
+    }
+
+    /// Called after every generation is evaluated, before it breeds.
+    /// Useful for logging, checkpointing or visualization.
+    pub fn on_generation(mut self, callback: impl FnMut(&GenerationStats, &Population) + 'a) -> Self {
+        self.callbacks.push(Box::new(callback));
+        self
+    }
+
+    /// Panics before evaluating anything if `population` has been emptied since `new`
+    pub fn run(mut self) -> EvolutionResult {
+        assert!(!self.population.members.is_empty(), "an Evolver needs at least one network to evolve");
+        let start = Instant::now();
+        let no_limits = self.target_fitness.is_none()
+            && self.max_generations.is_none()
+            && self.time_limit.is_none()
+            && self.stagnation_limit.is_none();
+        let max_generations = match self.max_generations {
+            Some(generations) => Some(generations),
+            None if no_limits => Some(DEFAULT_MAX_GENERATIONS),
+            None => None,
+        };
+
+        let mut history = Vec::new();
+        let mut checkpoint_error = None;
+
+        let stop_reason = loop {
+            for network in self.population.members.iter_mut() {
+                network.reset_state();
+                network.fitness = self.fitness.evaluate(network);
+                if let Some(parsimony) = &self.parsimony {
+                    network.fitness -= network.parsimony_penalty(parsimony);
+                }
+            }
+
+            let stats = self.generation_stats(start.elapsed());
+            self.update_hall_of_fame();
+            for callback in self.callbacks.iter_mut() {
+                callback(&stats, &self.population);
+            }
+            history.push(stats);
+
+            let best = self.hall_of_fame.first().map_or(f32::MIN, |c| c.fitness);
+            if self.target_fitness.is_some_and(|target| best >= target) {
+                break StopReason::TargetReached;
+            }
+            if max_generations.is_some_and(|limit| self.population.generation + 1 >= limit) {
+                break StopReason::GenerationLimit;
+            }
+            if self.time_limit.is_some_and(|limit| start.elapsed() >= limit) {
+                break StopReason::TimeLimit;
+            }
+            if self.stagnation_limit.is_some_and(|limit| self.since_improvement >= limit) {
+                break StopReason::Stagnated;
+            }
+
+            self.breed();
+
+            if let Some((every, path)) = &self.checkpoint {
+                if self.population.generation % every == 0 {
+                    if let Err(err) = self.save_checkpoint(path) {
+                        checkpoint_error = Some(err);
+                        break StopReason::CheckpointFailed;
+                    }
+                }
+            }
+        };
+
+        EvolutionResult {
+            // `from_boxed` rejects empty populations and at least one generation is evaluated
+            champion: self.hall_of_fame.first().cloned().expect("the hall of fame is never empty after a run"),
+            hall_of_fame: self.hall_of_fame,
+            stats: RunStats {
+                generations: history.len(),
+                elapsed: start.elapsed(),
+                stop_reason,
+                history,
+            },
+            population: self.population,
+            checkpoint_error,
+        }
+    }
+
+    /// Merges the freshly evaluated members into the hall of fame, skipping genomes already in it
+    fn update_hall_of_fame(&mut self) {
+        let previous_best = self.hall_of_fame.first().map(|c| c.fitness);
+        let prefer_smaller = self.population.config.prefer_smaller_on_ties;
+
+        let mut ranked: Vec<&minet> = self.population.members.iter().collect();
+        sort_best_first(&mut ranked, prefer_smaller);
+        for network in ranked.into_iter().take(self.hall_of_fame_size) {
+            let duplicate = self.hall_of_fame.iter().any(|famous| {
+                famous.genes == network.genes && (famous.input, famous.hidden) == (network.input, network.hidden)
+            });
+            let qualifies = self.hall_of_fame.len() < self.hall_of_fame_size
+                || self.hall_of_fame.last().is_some_and(|last| rank_order(network, last, prefer_smaller) == Ordering::Greater);
+            if !duplicate && qualifies {
+                self.hall_of_fame.push(network.clone());
+                sort_best_first(&mut self.hall_of_fame, prefer_smaller);
+                self.hall_of_fame.truncate(self.hall_of_fame_size);
+            }
+        }
+
+        let best = self.hall_of_fame.first().map(|c| c.fitness);
+        match previous_best {
+            Some(previous) if best.is_some_and(|best| best <= previous) => self.since_improvement += 1,
+            _ => self.since_improvement = 0,
+        }
+    }
+
+    fn breed(&mut self) {
+        let population = &mut self.population;
+        let target = population.members.len();
+        match &self.breeding {
+            Breeding::Truncation { survival_rate } => {
+                let members = std::mem::take(&mut population.members);
+                population.members =
+                    minet::crossbreed_population_with_rng(members, *survival_rate, target, &population.config, &mut self.rng);
+                population.generation += 1;
+            }
+            Breeding::Selection { selection, elitism } => {
+                population.members = minet::crossbreed_population_with_selection(
+                    &population.members,
+                    selection.as_ref(),
+                    *elitism,
+                    target,
+                    &population.config,
+                    &mut self.rng,
+                );
+                population.generation += 1;
+            }
+            Breeding::Speciated => population.evolve_with_rng(&mut self.rng),
+        }
+    }
+
+    fn generation_stats(&self, elapsed: Duration) -> GenerationStats {
+        let members = &self.population.members;
+        let count = members.len().max(1) as f32;
+        GenerationStats {
+            generation: self.population.generation,
+            best_fitness: members.iter().map(|m| m.fitness).fold(f32::MIN, f32::max),
+            mean_fitness: members.iter().map(|m| m.fitness).sum::<f32>() / count,
+            worst_fitness: members.iter().map(|m| m.fitness).fold(f32::MAX, f32::min),
+            mean_synapses: members.iter().map(|m| m.synapse_count() as f32).sum::<f32>() / count,
+            species: self.population.species.len(),
+            elapsed,
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use crate::{
+    minet,
+    minet_parsimony::{fittest, sort_best_first, sort_indices_best_first},
+    EvolutionConfig, Population,
+};
+use rand::{thread_rng, Rng};
+
+/// Which islands send migrants to which
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MigrationTopology {
+    /// Island i sends to island i + 1, the last one to the first
+    Ring,
+    /// Every island sends to every other island
+    FullyConnected,
+    /// Every island sends to one other island chosen at random each migration
+    Random,
+}
+
+/// Several populations that evolve independently and swap their best networks every few generations.
+/// Each island is a full `Population` with its own config and speciation settings.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IslandModel {
+    pub islands: Vec<Population>,
+    pub topology: MigrationTopology,
+    /// Generations between migrations, 0 disables migration
+    pub migration_interval: usize,
+    /// Best networks each island sends per migration, copies replace the receiver's worst members
+    pub migrants: usize,
+    pub generation: usize,
+}
+
+impl IslandModel {
+    /// Creates `count` islands of `size` networks sharing one config
+    pub fn new(count: usize, size: usize, input: usize, hidden: usize, output: usize, config: EvolutionConfig) -> Self {
+        IslandModel::new_with_rng(count, size, input, hidden, output, config, &mut thread_rng())
+    }
+
+    pub fn new_with_rng<R: Rng + ?Sized>(
+        count: usize,
+        size: usize,
+        input: usize,
+        hidden: usize,
+        output: usize,
+        config: EvolutionConfig,
+        rng: &mut R,
+    ) -> Self {
+        let islands = (0..count)
+            .map(|_| Population::new_with_rng(size, input, hidden, output, config.clone(), rng))
+            .collect();
+        IslandModel::from_islands(islands)
+    }
+
+    /// Wraps existing populations, e.g. ones with different configs
+    pub fn from_islands(islands: Vec<Population>) -> Self {
+        IslandModel {
+            islands,
+            topology: MigrationTopology::Ring,
+            migration_interval: 10,
+            migrants: 2,
+            generation: 0,
+        }
+    }
+
+    pub fn with_topology(mut self, topology: MigrationTopology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    pub fn with_migration(mut self, interval: usize, migrants: usize) -> Self {
+        self.migration_interval = interval;
+        self.migrants = migrants;
+        self
+    }
+
+    /// Every member of every island, for setting fitness
+    pub fn members_mut(&mut self) -> impl Iterator<Item = &mut minet> {
+        self.islands.iter_mut().flat_map(|island| island.members.iter_mut())
+    }
+
+    /// Returns the fittest member across all islands
+    pub fn best(&self) -> Option<&minet> {
+        let prefer_smaller = self.islands.iter().any(|island| island.config.prefer_smaller_on_ties);
+        fittest(self.islands.iter().filter_map(|island| island.best()), prefer_smaller)
+    }
+
+    /// Runs one generation: migrates if one is due, then evolves every island with `Population::evolve`.
+    /// Set every member's fitness first.
+    pub fn evolve(&mut self) {
+        self.evolve_with_rng(&mut thread_rng());
+    }
+
+    pub fn evolve_with_rng<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        if self.migration_due() {
+            self.migrate_with_rng(rng);
+        }
+        for island in self.islands.iter_mut() {
+            island.evolve_with_rng(rng);
+        }
+        self.generation += 1;
+    }
+
+    pub(crate) fn migration_due(&self) -> bool {
+        self.migration_interval > 0 && self.generation > 0 && self.generation % self.migration_interval == 0
+    }
+
+    /// Sends copies of each island's `migrants` fittest members along the topology.
+    /// Arrivals keep their fitness and replace the receiver's least fit members.
+    pub fn migrate(&mut self) {
+        self.migrate_with_rng(&mut thread_rng());
+    }
+
+    pub fn migrate_with_rng<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let count = self.islands.len();
+        if count < 2 || self.migrants == 0 {
+            return;
+        }
+
+        let emigrants: Vec<Vec<minet>> = self
+            .islands
+            .iter()
+            .map(|island| {
+                let mut ranked: Vec<&minet> = island.members.iter().collect();
+                sort_best_first(&mut ranked, island.config.prefer_smaller_on_ties);
+                ranked.into_iter().take(self.migrants).cloned().collect()
+            })
+            .collect();
+
+        let mut arrivals: Vec<Vec<minet>> = vec![Vec::new(); count];
+        for (source, group) in emigrants.into_iter().enumerate() {
+            let destinations: Vec<usize> = match self.topology {
+                MigrationTopology::Ring => vec![(source + 1) % count],
+                MigrationTopology::FullyConnected => (0..count).filter(|&d| d != source).collect(),
+                MigrationTopology::Random => {
+                    // Skip over the source so every other island is equally likely
+                    let pick = rng.gen_range(0..count - 1);
+                    vec![if pick >= source { pick + 1 } else { pick }]
+                }
+            };
+            for destination in destinations {
+                arrivals[destination].extend(group.iter().cloned());
+            }
+        }
+
+        for (island, incoming) in self.islands.iter_mut().zip(arrivals) {
+            let mut ranked: Vec<usize> = (0..island.members.len()).collect();
+            sort_indices_best_first(&mut ranked, &island.members, island.config.prefer_smaller_on_ties);
+            for (slot, immigrant) in ranked.into_iter().rev().zip(incoming) {
+                island.members[slot] = immigrant;
+            }
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use crate::{minet, minet_parsimony::rank_order, EvolutionConfig};
+use rand::{seq::SliceRandom, thread_rng, Rng};
+use std::cmp::Ordering;
+use std::fmt::Write as _;
+
+/// One axis of the MAP-Elites grid.
+/// Behavior values are split into `bins` equal ranges over [min, max], values outside land in the end bins.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BehaviorDimension {
+    pub min: f32,
+    pub max: f32,
+    /// Set through `new`, which keeps it at least 1
+    bins: usize,
+}
+
+impl BehaviorDimension {
+    pub fn new(min: f32, max: f32, bins: usize) -> Self {
+        BehaviorDimension { min, max, bins: bins.max(1) }
+    }
+
+    /// Number of bins, at least 1
+    pub fn bins(&self) -> usize {
+        // A deserialized dimension bypasses `new`
+        self.bins.max(1)
+    }
+
+    /// The bin a behavior value falls into
+    pub fn bin(&self, value: f32) -> usize {
+        let span = self.max - self.min;
+        if span <= 0.0 || value.is_nan() {
+            return 0;
+        }
+        let position = ((value - self.min) / span * self.bins() as f32).floor();
+        (position.max(0.0) as usize).min(self.bins() - 1)
+    }
+}
+
+/// Archive coverage and quality at a glance
+#[derive(Clone, Debug, PartialEq)]
+pub struct MapElitesSummary {
+    pub filled: usize,
+    pub cells: usize,
+    /// Filled fraction of the grid
+    pub coverage: f32,
+    /// Sum of every elite's fitness. Shift fitness to be non-negative for the score to reward coverage.
+    pub qd_score: f32,
+    pub best_fitness: f32,
+}
+
+/// A MAP-Elites quality-diversity archive: a grid over user-defined behavior dimensions holding the
+/// fittest network found for every cell. Networks are placed by their `behavior` and `fitness`.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MapElites {
+    pub dimensions: Vec<BehaviorDimension>,
+    pub config: EvolutionConfig,
+    /// Chance that a child is bred from two elites instead of mutated from one
+    pub crossover_probability: f64,
+    /// Flattened grid, the first dimension varies slowest
+    cells: Vec<Option<minet>>,
+}
+
+impl MapElites {
+    pub fn new(dimensions: Vec<BehaviorDimension>, config: EvolutionConfig) -> Self {
+        let cells = dimensions.iter().map(|dimension| dimension.bins()).product();
+        MapElites {
+            dimensions,
+            config,
+            crossover_probability: 0.5,
+            cells: vec![None; cells],
+        }
+    }
+
+    /// Index of the cell a behavior maps to, None if it has the wrong number of dimensions
+    pub fn cell_index(&self, behavior: &[f32]) -> Option<usize> {
+        if behavior.len() != self.dimensions.len() {
+            return None;
+        }
+        Some(
+            self.dimensions
+                .iter()
+                .zip(behavior)
+                .fold(0, |index, (dimension, &value)| index * dimension.bins() + dimension.bin(value)),
+        )
+    }
+
+    /// Bin of every dimension for a cell index
+    pub fn cell_coordinates(&self, mut index: usize) -> Vec<usize> {
+        let mut coordinates = vec![0; self.dimensions.len()];
+        for (coordinate, dimension) in coordinates.iter_mut().zip(&self.dimensions).rev() {
+            *coordinate = index % dimension.bins();
+            index /= dimension.bins();
+        }
+        coordinates
+    }
+
+    pub fn get(&self, coordinates: &[usize]) -> Option<&minet> {
+        if coordinates.len() != self.dimensions.len() {
+            return None;
+        }
+        let mut index = 0;
+        for (&coordinate, dimension) in coordinates.iter().zip(&self.dimensions) {
+            if coordinate >= dimension.bins() {
+                return None;
+            }
+            index = index * dimension.bins() + coordinate;
+        }
+        self.cells[index].as_ref()
+    }
+
+    /// Places the network in its cell if the cell is empty or holds a less fit network.
+    /// Returns true if the network was kept.
+    pub fn insert(&mut self, network: minet) -> bool {
+        let Some(index) = self.cell_index(&network.behavior) else {
+            return false;
+        };
+        let better = match &self.cells[index] {
+            Some(elite) => rank_order(&network, elite, self.config.prefer_smaller_on_ties) == Ordering::Greater,
+            None => true,
+        };
+        if better {
+            self.cells[index] = Some(network);
+        }
+        better
+    }
+
+    /// Fills the archive from `minet::initialize_population`.
+    /// `evaluate` returns each network's (fitness, behavior). Returns how many networks were kept.
+    pub fn seed<F: FnMut(&minet) -> (f32, Vec<f32>)>(
+        &mut self,
+        size: usize,
+        input: usize,
+        hidden: usize,
+        output: usize,
+        evaluate: F,
+    ) -> usize {
+        self.seed_with_rng(size, input, hidden, output, evaluate, &mut thread_rng())
+    }
+
+    pub fn seed_with_rng<F: FnMut(&minet) -> (f32, Vec<f32>), R: Rng + ?Sized>(
+        &mut self,
+        size: usize,
+        input: usize,
+        hidden: usize,
+        output: usize,
+        mut evaluate: F,
+        rng: &mut R,
+    ) -> usize {
+        let population = minet::initialize_population_with_rng(size, input, hidden, output, &self.config, rng);
+        population
+            .into_iter()
+            .map(|network| self.evaluate_and_insert(network, &mut evaluate))
+            .filter(|&kept| kept)
+            .count()
+    }
+
+    /// Breeds `batch` children from uniformly chosen elites, with `crossbreed` or by mutating a copy,
+    /// and inserts each one. Returns how many children were kept. Does nothing on an empty archive.
+    pub fn generation<F: FnMut(&minet) -> (f32, Vec<f32>)>(&mut self, batch: usize, evaluate: F) -> usize {
+        self.generation_with_rng(batch, evaluate, &mut thread_rng())
+    }
+
+    pub fn generation_with_rng<F: FnMut(&minet) -> (f32, Vec<f32>), R: Rng + ?Sized>(
+        &mut self,
+        batch: usize,
+        mut evaluate: F,
+        rng: &mut R,
+    ) -> usize {
+        // Parents are drawn from the cells that were filled before the batch started
+        let filled: Vec<usize> = (0..self.cells.len()).filter(|&i| self.cells[i].is_some()).collect();
+        if filled.is_empty() {
+            return 0;
+        }
+        let mut kept = 0;
+        for _ in 0..batch {
+            let first = *filled.choose(rng).unwrap();
+            let parent = self.cells[first].as_ref().unwrap();
+            let child = if rng.gen_bool(self.crossover_probability) {
+                let second = *filled.choose(rng).unwrap();
+                parent.crossbreed_with_rng(self.cells[second].as_ref().unwrap(), &self.config, rng)
+            } else {
+                let mut child = parent.clone();
+                child.mutate_with_rng(&self.config, rng);
+                child
+            };
+            if self.evaluate_and_insert(child, &mut evaluate) {
+                kept += 1;
+            }
+        }
+        kept
+    }
+
+    fn evaluate_and_insert<F: FnMut(&minet) -> (f32, Vec<f32>)>(&mut self, mut network: minet, evaluate: &mut F) -> bool {
+        let (fitness, behavior) = evaluate(&network);
+        network.fitness = fitness;
+        network.behavior = behavior;
+        self.insert(network)
+    }
+
+    /// Every filled cell as (coordinates, elite)
+    pub fn elites(&self) -> impl Iterator<Item = (Vec<usize>, &minet)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter_map(|(i, cell)| cell.as_ref().map(|elite| (self.cell_coordinates(i), elite)))
+    }
+
+    pub fn summary(&self) -> MapElitesSummary {
+        let filled = self.elites().count();
+        let cells = self.cells.len();
+        MapElitesSummary {
+            filled,
+            cells,
+            coverage: if cells > 0 { filled as f32 / cells as f32 } else { 0.0 },
+            qd_score: self.elites().map(|(_, elite)| elite.fitness).sum(),
+            best_fitness: self.elites().map(|(_, elite)| elite.fitness).fold(f32::MIN, f32::max),
+        }
+    }
+
+    /// The filled archive as text, one elite per line:
+    /// comma separated coordinates, fitness, comma separated behavior and the `encode`d genome
+    pub fn export(&self) -> String {
+        let mut out = String::from("# cell fitness behavior genome\n");
+        for (coordinates, elite) in self.elites() {
+            let coordinates: Vec<String> = coordinates.iter().map(|c| c.to_string()).collect();
+            let behavior: Vec<String> = elite.behavior.iter().map(|b| b.to_string()).collect();
+            // Writing to a String cannot fail
+            let _ = writeln!(
+                out,
+                "{} {} {} {}",
+                coordinates.join(","),
+                elite.fitness,
+                behavior.join(","),
+                elite.encode()
+            );
+        }
+        out
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{minet, Activation, EvolutionConfig};
+use rand::{seq::SliceRandom, Rng};
+
+impl minet {
+    /// Splits a random forward synapse source -> target into source -> new -> target.
+    /// The incoming synapse gets weight 1.0 and the outgoing one keeps the old weight,
+    /// so the network behaves much like it did before the split.
+    pub(crate) fn neuron_add_random<R: Rng + ?Sized>(&mut self, config: &EvolutionConfig, rng: &mut R) {
+        if self.hidden >= config.max_hidden {
+            return;
+        }
+
+        let forward_synapses: Vec<(usize, usize)> = self
+            .genes
+            .iter()
+            .enumerate()
+            .flat_map(|(source, gene)| {
+                gene.1
+                    .iter()
+                    .enumerate()
+                    .filter(move |&(_, &(target, _))| target > source)
+                    .map(move |(j, _)| (source, j))
+            })
+            .collect();
+
+        let Some(&(source, synapse_idx)) = forward_synapses.choose(rng) else {
+            return;
+        };
+        let (target, weight) = self.genes[source].1.remove(synapse_idx);
+
+        // The new neuron must sit after the source, before the target and inside the hidden layer
+        let lowest = (source + 1).max(self.input);
+        let highest = target.min(self.input + self.hidden);
+        let index = rng.gen_range(lowest..=highest);
+
+        self.neuron_insert(index);
+        self.genes[source].1.push((index, 1.0));
+        self.genes[index].1.push((target + 1, weight));
+    }
+
+    /// Deletes a random hidden neuron that either receives nothing or sends nothing forward
+    pub(crate) fn neuron_remove_random<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let unused: Vec<usize> = (self.input..self.input + self.hidden)
+            .filter(|&i| !self.neuron_has_input(i) || !self.neuron_has_output(i))
+            .collect();
+
+        if let Some(&index) = unused.choose(rng) {
+            self.neuron_remove(index);
+        }
+    }
+
+    /// Inserts an unconnected hidden neuron at the given index, shifting every later neuron up by one
+    pub fn neuron_insert(&mut self, index: usize) {
+        assert!(
+            index >= self.input && index <= self.input + self.hidden,
+            "hidden neurons must be inserted between the inputs and outputs"
+        );
+        for gene in self.genes.iter_mut() {
+            for synapse in gene.1.iter_mut() {
+                if synapse.0 >= index {
+                    synapse.0 += 1;
+                }
+            }
+        }
+        self.genes.insert(index, (0.0, Vec::new(), Activation::Tanh));
+        if self.state.len() >= index {
+            self.state.insert(index, 0.0);
+        }
+        if self.is_continuous() && index <= self.time_constants.len() {
+            self.time_constants.insert(index, 1.0);
+        }
+        if let Some(modulator) = self.plasticity.as_mut().and_then(|plasticity| plasticity.modulator.as_mut()) {
+            if *modulator >= index {
+                *modulator += 1;
+            }
+        }
+        self.weight_changes.clear();
+        self.hidden += 1;
+    }
+
+    /// Removes the hidden neuron at the given index along with every synapse into it,
+    /// shifting every later neuron down by one
+    pub fn neuron_remove(&mut self, index: usize) {
+        assert!(
+            index >= self.input && index < self.input + self.hidden,
+            "only hidden neurons can be removed"
+        );
+        self.genes.remove(index);
+        for gene in self.genes.iter_mut() {
+            gene.1.retain(|&(target, _)| target != index);
+            for synapse in gene.1.iter_mut() {
+                if synapse.0 > index {
+                    synapse.0 -= 1;
+                }
+            }
+        }
+        if index < self.state.len() {
+            self.state.remove(index);
+        }
+        if index < self.time_constants.len() {
+            self.time_constants.remove(index);
+        }
+        if let Some(plasticity) = self.plasticity.as_mut() {
+            plasticity.modulator = match plasticity.modulator {
+                Some(modulator) if modulator == index => None,
+                Some(modulator) if modulator > index => Some(modulator - 1),
+                modulator => modulator,
+            };
+        }
+        self.weight_changes.clear();
+        self.hidden -= 1;
+    }
+
+    /// Returns true if any other neuron has a synapse into this one
+    fn neuron_has_input(&self, index: usize) -> bool {
+        self.genes
+            .iter()
+            .enumerate()
+            .any(|(source, gene)| source != index && gene.1.iter().any(|&(target, _)| target == index))
+    }
+
+    /// Returns true if this neuron has a synapse into any other neuron
+    fn neuron_has_output(&self, index: usize) -> bool {
+        self.genes[index].1.iter().any(|&(target, _)| target != index)
+    }
+
+    /// Maps a neuron index in this network to the matching neuron in a network with a different hidden count.
+    /// Inputs and hidden neurons line up from the start, outputs line up from the end.
+    /// Returns None for hidden neurons the other network does not have.
+    pub fn counterpart_index(&self, index: usize, other: &minet) -> Option<usize> {
+        let output_start = self.input + self.hidden;
+        if index < self.input {
+            Some(index)
+        } else if index < output_start {
+            let hidden_idx = index - self.input;
+            (hidden_idx < other.hidden).then_some(other.input + hidden_idx)
+        } else {
+            Some(other.input + other.hidden + (index - output_start))
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use crate::minet;
+
+/// Parameters for novelty search
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NoveltyConfig {
+    /// Novelty is the mean distance to this many nearest behaviors
+    pub k: usize,
+    /// Behaviors at least this novel are added to the archive
+    pub archive_threshold: f32,
+    /// The oldest behaviors are dropped once the archive holds this many
+    pub max_archive_size: usize,
+    /// Share of fitness in the blended score, 0 for pure novelty, 1 for pure fitness
+    pub fitness_weight: f32,
+}
+
+impl Default for NoveltyConfig {
+    fn default() -> Self {
+        NoveltyConfig {
+            k: 15,
+            archive_threshold: 1.0,
+            max_archive_size: 1000,
+            fitness_weight: 0.0,
+        }
+    }
+}
+
+/// Remembers novel behaviors across generations and scores networks by how far their
+/// behavior lies from everything seen so far
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NoveltyArchive {
+    pub config: NoveltyConfig,
+    /// Archived behaviors, oldest first
+    pub behaviors: Vec<Vec<f32>>,
+}
+
+impl NoveltyArchive {
+    pub fn new(config: NoveltyConfig) -> Self {
+        NoveltyArchive { config, behaviors: Vec::new() }
+    }
+
+    /// Mean Euclidean distance from `behavior` to its `k` nearest neighbours among `others` and the archive
+    pub fn sparseness(&self, behavior: &[f32], others: &[&[f32]]) -> f32 {
+        let mut distances: Vec<f32> = others
+            .iter()
+            .copied()
+            .chain(self.behaviors.iter().map(|b| b.as_slice()))
+            .map(|other| behavior_distance(behavior, other))
+            .collect();
+        let k = self.config.k.max(1).min(distances.len());
+        if k == 0 {
+            return 0.0;
+        }
+        distances.select_nth_unstable_by(k - 1, |a, b| a.total_cmp(b));
+        distances[..k].iter().sum::<f32>() / k as f32
+    }
+
+    /// Scores every network by the novelty of its `behavior` against the rest of the population and the archive,
+    /// archives the behaviors that pass `archive_threshold`, and returns the novelty scores.
+    /// Each network's fitness is replaced by `fitness_weight * fitness + (1 - fitness_weight) * novelty`,
+    /// ready for `crossbreed_population` or any `Selection`.
+    pub fn score(&mut self, population: &mut [minet]) -> Vec<f32> {
+        let behaviors: Vec<&[f32]> = population.iter().map(|network| network.behavior.as_slice()).collect();
+        let novelty: Vec<f32> = (0..behaviors.len())
+            .map(|i| {
+                let others: Vec<&[f32]> = behaviors
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .map(|(_, &behavior)| behavior)
+                    .collect();
+                self.sparseness(behaviors[i], &others)
+            })
+            .collect();
+
+        for (network, &score) in population.iter().zip(&novelty) {
+            if score >= self.config.archive_threshold {
+                self.behaviors.push(network.behavior.clone());
+            }
+        }
+        let excess = self.behaviors.len().saturating_sub(self.config.max_archive_size);
+        self.behaviors.drain(..excess);
+
+        let weight = self.config.fitness_weight.clamp(0.0, 1.0);
+        for (network, &score) in population.iter_mut().zip(&novelty) {
+            network.fitness = weight * network.fitness + (1.0 - weight) * score;
+        }
+        novelty
+    }
+}
+
+/// Euclidean distance, dimensions missing from the shorter descriptor count as zero
+fn behavior_distance(a: &[f32], b: &[f32]) -> f32 {
+    let length = a.len().max(b.len());
+    (0..length)
+        .map(|i| {
+            let difference = a.get(i).copied().unwrap_or(0.0) - b.get(i).copied().unwrap_or(0.0);
+            difference * difference
+        })
+        .sum::<f32>()
+        .sqrt()
+}
@@ -0,0 +1,165 @@
+use crate::{minet, EvolutionConfig};
+use rand::{thread_rng, Rng};
+
+impl minet {
+    /// Returns true if this network scores at least as well as `other` on every objective
+    /// and strictly better on at least one
+    pub fn dominates(&self, other: &minet) -> bool {
+        let mut better = false;
+        for (&mine, &theirs) in self.objectives.iter().zip(&other.objectives) {
+            if mine < theirs {
+                return false;
+            }
+            better |= mine > theirs;
+        }
+        better
+    }
+
+    /// Splits the population into Pareto fronts by objectives.
+    /// The first front holds every network no other network dominates, each later front
+    /// holds the networks dominated only by earlier fronts.
+    pub fn non_dominated_sort(population: &[minet]) -> Vec<Vec<usize>> {
+        let n = population.len();
+        let mut dominated: Vec<Vec<usize>> = vec![Vec::new(); n];
+        let mut domination_count = vec![0usize; n];
+        for a in 0..n {
+            for b in a + 1..n {
+                if population[a].dominates(&population[b]) {
+                    dominated[a].push(b);
+                    domination_count[b] += 1;
+                } else if population[b].dominates(&population[a]) {
+                    dominated[b].push(a);
+                    domination_count[a] += 1;
+                }
+            }
+        }
+
+        let mut fronts = Vec::new();
+        let mut current: Vec<usize> = (0..n).filter(|&i| domination_count[i] == 0).collect();
+        while !current.is_empty() {
+            let mut next = Vec::new();
+            for &i in &current {
+                for &j in &dominated[i] {
+                    domination_count[j] -= 1;
+                    if domination_count[j] == 0 {
+                        next.push(j);
+                    }
+                }
+            }
+            fronts.push(current);
+            current = next;
+        }
+        fronts
+    }
+
+    /// Crowding distance of every network in `front`, in the same order.
+    /// Networks at either end of any objective get infinity, the rest the sum over objectives of
+    /// the normalized gap between their neighbours. Larger means less crowded.
+    pub fn crowding_distance(population: &[minet], front: &[usize]) -> Vec<f32> {
+        let mut distance = vec![0.0; front.len()];
+        let objectives = front.iter().map(|&i| population[i].objectives.len()).min().unwrap_or(0);
+        let mut order: Vec<usize> = (0..front.len()).collect();
+        for objective in 0..objectives {
+            let value = |k: usize| population[front[k]].objectives[objective];
+            order.sort_by(|&a, &b| value(a).total_cmp(&value(b)));
+
+            let (first, last) = (order[0], order[front.len() - 1]);
+            distance[first] = f32::INFINITY;
+            distance[last] = f32::INFINITY;
+            let range = value(last) - value(first);
+            if range <= 0.0 {
+                continue;
+            }
+            for window in order.windows(3) {
+                distance[window[1]] += (value(window[2]) - value(window[0])) / range;
+            }
+        }
+        distance
+    }
+
+    /// Clones of the networks no other network dominates
+    pub fn pareto_front(population: &[minet]) -> Vec<minet> {
+        match minet::non_dominated_sort(population).first() {
+            Some(front) => front.iter().map(|&i| population[i].clone()).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// One NSGA-II generation.
+    /// `population` must already have its objectives set. An equal number of children is bred from parents
+    /// picked by binary tournament on (front, crowding distance), each child is scored with `evaluate`,
+    /// and the best half of parents and children by the same order survives.
+    pub fn nsga2_generation<F: FnMut(&minet) -> Vec<f32>>(population: Vec<minet>, evaluate: F) -> Vec<minet> {
+        minet::nsga2_generation_with_config(population, evaluate, &EvolutionConfig::default())
+    }
+
+    pub fn nsga2_generation_with_config<F: FnMut(&minet) -> Vec<f32>>(
+        population: Vec<minet>,
+        evaluate: F,
+        config: &EvolutionConfig,
+    ) -> Vec<minet> {
+        minet::nsga2_generation_with_rng(population, evaluate, config, &mut thread_rng())
+    }
+
+    pub fn nsga2_generation_with_rng<F: FnMut(&minet) -> Vec<f32>, R: Rng + ?Sized>(
+        population: Vec<minet>,
+        mut evaluate: F,
+        config: &EvolutionConfig,
+        rng: &mut R,
+    ) -> Vec<minet> {
+        let size = population.len();
+        if size == 0 {
+            return population;
+        }
+
+        let (front_rank, crowding) = crowded_ranking(&population);
+        let tournament = |rng: &mut R| {
+            let (a, b) = (rng.gen_range(0..size), rng.gen_range(0..size));
+            if crowded_better(a, b, &front_rank, &crowding) { a } else { b }
+        };
+
+        let mut combined = population.clone();
+        for _ in 0..size {
+            let first = tournament(rng);
+            let second = tournament(rng);
+            let mut child = population[first].crossbreed_with_rng(&population[second], config, rng);
+            child.objectives = evaluate(&child);
+            combined.push(child);
+        }
+
+        let mut survivors = Vec::with_capacity(size);
+        for front in minet::non_dominated_sort(&combined) {
+            if survivors.len() + front.len() <= size {
+                survivors.extend(front);
+                continue;
+            }
+            let distance = minet::crowding_distance(&combined, &front);
+            let mut order: Vec<usize> = (0..front.len()).collect();
+            order.sort_by(|&a, &b| distance[b].total_cmp(&distance[a]));
+            survivors.extend(order.into_iter().take(size - survivors.len()).map(|k| front[k]));
+            break;
+        }
+
+        let mut combined: Vec<Option<minet>> = combined.into_iter().map(Some).collect();
+        survivors.into_iter().filter_map(|i| combined[i].take()).collect()
+    }
+}
+
+/// Front index and crowding distance of every network
+fn crowded_ranking(population: &[minet]) -> (Vec<usize>, Vec<f32>) {
+    let mut front_rank = vec![0; population.len()];
+    let mut crowding = vec![0.0; population.len()];
+    for (rank, front) in minet::non_dominated_sort(population).into_iter().enumerate() {
+        let distance = minet::crowding_distance(population, &front);
+        for (k, &i) in front.iter().enumerate() {
+            front_rank[i] = rank;
+            crowding[i] = distance[k];
+        }
+    }
+    (front_rank, crowding)
+}
+
+/// NSGA-II's crowded comparison: an earlier front wins, then the less crowded network
+fn crowded_better(a: usize, b: usize, front_rank: &[usize], crowding: &[f32]) -> bool {
+    front_rank[a] < front_rank[b] || (front_rank[a] == front_rank[b] && crowding[a] > crowding[b])
+}
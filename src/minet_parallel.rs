@@ -0,0 +1,109 @@
+use crate::{minet, EvolutionConfig, IslandModel, Population};
+use rand::{seq::SliceRandom, thread_rng, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use rayon::prelude::*;
+
+impl minet {
+    /// Scores every network in parallel with the given fitness function
+    pub fn evaluate_population_parallel<F>(population: &mut [minet], fitness: F)
+    where
+        F: Fn(&minet) -> f32 + Sync,
+    {
+        population.par_iter_mut().for_each(|network| network.fitness = fitness(network));
+    }
+
+    /// Parallel version of `crossbreed_population`
+    pub fn crossbreed_population_parallel(
+        population: Vec<minet>,
+        survival_rate: f32,
+        target_population: usize,
+        config: &EvolutionConfig,
+    ) -> Vec<minet> {
+        minet::crossbreed_population_parallel_with_rng(population, survival_rate, target_population, config, &mut thread_rng())
+    }
+
+    /// Parallel version of `crossbreed_population_with_rng`.
+    /// One seed per child is drawn from `rng` up front and every child is bred with its own
+    /// generator, so the result depends only on `rng` and not on how rayon schedules the work.
+    pub fn crossbreed_population_parallel_with_rng<R: Rng + ?Sized>(
+        mut population: Vec<minet>,
+        survival_rate: f32,
+        target_population: usize,
+        config: &EvolutionConfig,
+        rng: &mut R,
+    ) -> Vec<minet> {
+        minet::truncate_to_survivors(&mut population, survival_rate, config);
+        let new_target = target_population - population.len();
+        let seeds: Vec<u64> = (0..new_target).map(|_| rng.gen()).collect();
+
+        let survivors = &population;
+        let children: Vec<minet> = seeds
+            .into_par_iter()
+            .map(|seed| {
+                let mut child_rng = ChaCha8Rng::seed_from_u64(seed);
+                let parent1 = survivors.choose(&mut child_rng).unwrap();
+                let parent2 = survivors.choose(&mut child_rng).unwrap();
+                parent1.crossbreed_with_rng(parent2, config, &mut child_rng)
+            })
+            .collect();
+
+        population.extend(children);
+        population
+    }
+
+    /// Scores the population in parallel, then breeds the next generation in parallel
+    pub fn evaluate_and_crossbreed_parallel<F, R>(
+        mut population: Vec<minet>,
+        fitness: F,
+        survival_rate: f32,
+        target_population: usize,
+        config: &EvolutionConfig,
+        rng: &mut R,
+    ) -> Vec<minet>
+    where
+        F: Fn(&minet) -> f32 + Sync,
+        R: Rng + ?Sized,
+    {
+        minet::evaluate_population_parallel(&mut population, fitness);
+        minet::crossbreed_population_parallel_with_rng(population, survival_rate, target_population, config, rng)
+    }
+}
+
+impl Population {
+    /// Scores every member in parallel with the given fitness function
+    pub fn evaluate_parallel<F>(&mut self, fitness: F)
+    where
+        F: Fn(&minet) -> f32 + Sync,
+    {
+        minet::evaluate_population_parallel(&mut self.members, fitness);
+    }
+}
+
+impl IslandModel {
+    /// Scores every member of every island in parallel with the given fitness function
+    pub fn evaluate_parallel<F>(&mut self, fitness: F)
+    where
+        F: Fn(&minet) -> f32 + Sync,
+    {
+        self.islands.par_iter_mut().for_each(|island| island.evaluate_parallel(&fitness));
+    }
+
+    /// Parallel version of `evolve`, one island per task
+    pub fn evolve_parallel(&mut self) {
+        self.evolve_parallel_with_rng(&mut thread_rng());
+    }
+
+    /// Parallel version of `evolve_with_rng`.
+    /// Every island evolves with its own generator seeded from `rng`, so the result does not depend on the thread count.
+    pub fn evolve_parallel_with_rng<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        if self.migration_due() {
+            self.migrate_with_rng(rng);
+        }
+        let seeds: Vec<u64> = self.islands.iter().map(|_| rng.gen()).collect();
+        self.islands
+            .par_iter_mut()
+            .zip(seeds)
+            .for_each(|(island, seed)| island.evolve_with_rng(&mut ChaCha8Rng::seed_from_u64(seed)));
+        self.generation += 1;
+    }
+}
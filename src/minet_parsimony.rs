@@ -0,0 +1,175 @@
+use crate::{minet, Selection, Tournament};
+use rand::{Rng, RngCore};
+use std::{borrow::Borrow, cmp::Ordering};
+
+/// Fitness penalty for network size, subtracted from every evaluated fitness by `Evolver::parsimony`.
+/// Sizes are measured after `simplify`, so inert structure is neither rewarded nor penalized.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Parsimony {
+    /// Subtracted once per active synapse
+    pub synapse_penalty: f32,
+    /// Subtracted once per active hidden or output neuron
+    pub neuron_penalty: f32,
+}
+
+impl minet {
+    /// Active neurons plus active synapses, as counted by `effective_size`
+    pub fn complexity(&self) -> usize {
+        let (neurons, synapses) = self.effective_size();
+        neurons + synapses
+    }
+
+    /// The amount `parsimony` takes off this network's fitness
+    pub fn parsimony_penalty(&self, parsimony: &Parsimony) -> f32 {
+        let (neurons, synapses) = self.effective_size();
+        parsimony.neuron_penalty * neurons as f32 + parsimony.synapse_penalty * synapses as f32
+    }
+
+    /// Orders networks by fitness, with NaN below every number, breaking ties in favour of the smaller network.
+    /// `Greater` means `self` is the better network. Rankings in the crate use this order when
+    /// `EvolutionConfig::prefer_smaller_on_ties` is set, and fitness alone otherwise.
+    /// Ties cost a `complexity` call on each network, rankings in the crate measure each network at most once.
+    pub fn compare_fitness(&self, other: &minet) -> Ordering {
+        fitness_order(self.fitness, other.fitness).then_with(|| other.complexity().cmp(&self.complexity()))
+    }
+}
+
+/// Orders fitness values with NaN below every number, so a failed evaluation never ranks best
+pub(crate) fn fitness_order(a: f32, b: f32) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (false, false) => a.total_cmp(&b),
+        (a_nan, b_nan) => b_nan.cmp(&a_nan),
+    }
+}
+
+/// `compare_fitness` if `prefer_smaller` is set, otherwise fitness alone
+pub(crate) fn rank_order(a: &minet, b: &minet, prefer_smaller: bool) -> Ordering {
+    if prefer_smaller {
+        a.compare_fitness(b)
+    } else {
+        fitness_order(a.fitness, b.fitness)
+    }
+}
+
+/// Sorts networks best first in `rank_order`
+pub(crate) fn sort_best_first<N: Borrow<minet>>(networks: &mut [N], prefer_smaller: bool) {
+    sort_by_rank(networks, prefer_smaller, |network| network.borrow().fitness, |network| network.borrow().complexity());
+}
+
+/// Sorts indices into `population` best first in `rank_order`
+pub(crate) fn sort_indices_best_first(indices: &mut [usize], population: &[minet], prefer_smaller: bool) {
+    sort_by_rank(indices, prefer_smaller, |&i| population[i].fitness, |&i| population[i].complexity());
+}
+
+/// Complexity simplifies a copy of the network, so it is only measured inside runs of equal fitness,
+/// once per network. Both sorts are stable, so the order matches sorting by `rank_order`.
+fn sort_by_rank<T>(
+    items: &mut [T],
+    prefer_smaller: bool,
+    fitness: impl Fn(&T) -> f32,
+    complexity: impl Fn(&T) -> usize,
+) {
+    items.sort_by(|a, b| fitness_order(fitness(b), fitness(a)));
+    if !prefer_smaller {
+        return;
+    }
+    let mut start = 0;
+    while start < items.len() {
+        let best = fitness(&items[start]);
+        let tied = items[start..].iter().take_while(|&item| fitness_order(fitness(item), best).is_eq()).count();
+        if tied > 1 {
+            items[start..start + tied].sort_by_cached_key(&complexity);
+        }
+        start += tied;
+    }
+}
+
+/// The network `max_by(rank_order)` would pick, measuring complexity only for those tied on the best fitness
+pub(crate) fn fittest<'a>(networks: impl IntoIterator<Item = &'a minet>, prefer_smaller: bool) -> Option<&'a minet> {
+    let networks: Vec<&minet> = networks.into_iter().collect();
+    let best = networks.iter().map(|network| network.fitness).max_by(|&a, &b| fitness_order(a, b))?;
+    // max_by keeps the last of equal elements, so search the ties from the back
+    let mut tied = networks.into_iter().rev().filter(|network| fitness_order(network.fitness, best).is_eq());
+    if prefer_smaller {
+        tied.min_by_key(|network| network.complexity())
+    } else {
+        tied.next()
+    }
+}
+
+/// Double tournament: two fitness tournaments of `size` contestants, then the smaller of the two winners
+/// is picked with probability `parsimony`, which should lie in [0.5, 1].
+/// 0.5 ignores size, 1 always prefers the smaller network.
+#[derive(Clone, Debug)]
+pub struct DoubleTournament {
+    pub size: usize,
+    pub parsimony: f32,
+}
+
+impl Selection for DoubleTournament {
+    fn select(&self, population: &[minet], rng: &mut dyn RngCore) -> usize {
+        let fitness_tournament = Tournament { size: self.size };
+        let first = fitness_tournament.select(population, rng);
+        let second = fitness_tournament.select(population, rng);
+
+        let (smaller, larger) = match population[first].complexity().cmp(&population[second].complexity()) {
+            Ordering::Greater => (second, first),
+            Ordering::Less => (first, second),
+            Ordering::Equal => return first,
+        };
+        if rng.gen_bool(self.parsimony.clamp(0.0, 1.0) as f64) {
+            smaller
+        } else {
+            larger
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Networks with the given fitness, the first of each tied pair larger than the second
+    fn networks(fitness: &[f32]) -> Vec<minet> {
+        fitness
+            .iter()
+            .enumerate()
+            .map(|(i, &fitness)| {
+                let mut network = minet::new(2, 3, 1);
+                network.fitness = fitness;
+                if i % 2 == 1 {
+                    for gene in network.genes.iter_mut() {
+                        gene.1.clear();
+                    }
+                }
+                network
+            })
+            .collect()
+    }
+
+    #[test]
+    fn nan_fitness_ranks_last() {
+        let population = networks(&[f32::NAN, 1.0, -5.0, f32::NAN, 2.0]);
+        for prefer_smaller in [false, true] {
+            let mut ranked: Vec<usize> = (0..population.len()).collect();
+            sort_indices_best_first(&mut ranked, &population, prefer_smaller);
+            assert_eq!(&ranked[..3], [4, 1, 2]);
+            assert_eq!(fittest(&population, prefer_smaller).unwrap().fitness, 2.0);
+        }
+        assert_eq!(population[0].compare_fitness(&population[2]), Ordering::Less);
+        assert!(fittest(&networks(&[f32::NAN]), false).unwrap().fitness.is_nan());
+    }
+
+    #[test]
+    fn ties_keep_their_order_unless_smaller_is_preferred() {
+        let population = networks(&[1.0, 1.0, 0.5]);
+        assert!(population[0].complexity() > population[1].complexity());
+
+        let mut ranked: Vec<usize> = (0..population.len()).collect();
+        sort_indices_best_first(&mut ranked, &population, false);
+        assert_eq!(ranked, [0, 1, 2]);
+        sort_indices_best_first(&mut ranked, &population, true);
+        assert_eq!(ranked, [1, 0, 2]);
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{minet, sample_normal, EvolutionConfig};
+use rand::Rng;
+
+/// Rule coefficients are kept in [-MAX_COEFFICIENT, MAX_COEFFICIENT]
+const MAX_COEFFICIENT: f32 = 1.0;
+/// Learning rate given to a newly evolved rule
+const INITIAL_LEARNING_RATE: f32 = 0.1;
+
+/// ABCD Hebbian rule. After every stateful pass each synapse changes by
+/// `learning_rate * modulation * (a * pre * post + b * pre + c * post + d)`,
+/// where pre and post are the activations of its source and target.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HebbianRule {
+    pub learning_rate: f32,
+    /// Correlation term
+    pub a: f32,
+    /// Presynaptic term
+    pub b: f32,
+    /// Postsynaptic term
+    pub c: f32,
+    /// Constant drift
+    pub d: f32,
+}
+
+impl HebbianRule {
+    pub fn new(learning_rate: f32, a: f32, b: f32, c: f32, d: f32) -> Self {
+        HebbianRule { learning_rate, a, b, c, d }
+    }
+
+    /// Plain Hebbian learning, weights grow where source and target fire together
+    pub fn hebbian(learning_rate: f32) -> Self {
+        HebbianRule::new(learning_rate, 1.0, 0.0, 0.0, 0.0)
+    }
+
+    /// Weight change of one synapse
+    pub fn delta(&self, pre: f32, post: f32, modulation: f32) -> f32 {
+        self.learning_rate * modulation * (self.a * pre * post + self.b * pre + self.c * post + self.d)
+    }
+
+    fn coefficients_mut(&mut self) -> [&mut f32; 5] {
+        [&mut self.learning_rate, &mut self.a, &mut self.b, &mut self.c, &mut self.d]
+    }
+}
+
+/// Evolved lifetime learning, shared by every synapse of a network.
+/// Only `forward_stateful` applies it, and `reset_state` undoes everything learned.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Plasticity {
+    pub rule: HebbianRule,
+    /// Neuron whose activation scales every change, e.g. an input carrying reward. None for plain ABCD learning.
+    pub modulator: Option<usize>,
+    /// Learned weights are clamped to [-max_weight, max_weight]
+    pub max_weight: f32,
+}
+
+impl Plasticity {
+    pub fn new(rule: HebbianRule) -> Self {
+        Plasticity { rule, modulator: None, max_weight: 10.0 }
+    }
+
+    pub fn with_modulator(mut self, neuron: usize) -> Self {
+        self.modulator = Some(neuron);
+        self
+    }
+
+    fn random<R: Rng + ?Sized>(config: &EvolutionConfig, rng: &mut R) -> Self {
+        let mut coefficient = || sample_normal(config.initial_weight_std_dev, rng).clamp(-MAX_COEFFICIENT, MAX_COEFFICIENT);
+        let rule = HebbianRule::new(INITIAL_LEARNING_RATE, coefficient(), coefficient(), coefficient(), coefficient());
+        Plasticity { rule, modulator: None, max_weight: config.max_weight }
+    }
+}
+
+impl minet {
+    /// Returns true if the network learns during `forward_stateful`
+    pub fn is_plastic(&self) -> bool {
+        self.plasticity.is_some()
+    }
+
+    /// Outgoing synapses of a neuron as (target, weight), including the weight changes learned this lifetime
+    pub(crate) fn lifetime_synapses(&self, source: usize) -> impl Iterator<Item = (usize, f32)> + '_ {
+        let changes = self.weight_changes.get(source).map_or(&[][..], Vec::as_slice);
+        self.genes[source]
+            .1
+            .iter()
+            .enumerate()
+            .map(move |(j, &(target, weight))| (target, weight + changes.get(j).copied().unwrap_or(0.0)))
+    }
+
+    /// Updates the learned weight changes from one pass. Recurrent synapses see the
+    /// previous tick's activation of their source, as they did during the pass.
+    pub(crate) fn apply_plasticity(&mut self, activations: &[f32], previous: &[f32]) {
+        let Some(plasticity) = &self.plasticity else {
+            return;
+        };
+        let modulation = plasticity
+            .modulator
+            .map_or(1.0, |neuron| activations.get(neuron).copied().unwrap_or(0.0));
+
+        self.weight_changes.resize(self.genes.len(), Vec::new());
+        for (source, (gene, changes)) in self.genes.iter().zip(self.weight_changes.iter_mut()).enumerate() {
+            changes.resize(gene.1.len(), 0.0);
+            for (&(target, weight), change) in gene.1.iter().zip(changes.iter_mut()) {
+                let pre = if target <= source {
+                    previous.get(source).copied().unwrap_or(0.0)
+                } else {
+                    activations[source]
+                };
+                let learned = weight + *change + plasticity.rule.delta(pre, activations[target], modulation);
+                *change = learned.clamp(-plasticity.max_weight, plasticity.max_weight) - weight;
+            }
+        }
+    }
+
+    /// Perturbs the rule like a bias, occasionally moves the modulator, and rarely evolves a rule from scratch
+    pub(crate) fn mutate_plasticity<R: Rng + ?Sized>(&mut self, config: &EvolutionConfig, rng: &mut R) {
+        let neurons = self.genes.len();
+        match &mut self.plasticity {
+            None => {
+                if config.add_plasticity_probability > 0.0 && rng.gen_bool(config.add_plasticity_probability) {
+                    self.plasticity = Some(Plasticity::random(config, rng));
+                }
+            }
+            Some(plasticity) => {
+                for coefficient in plasticity.rule.coefficients_mut() {
+                    *coefficient += sample_normal(config.plasticity_std_dev, rng);
+                    *coefficient = coefficient.clamp(-MAX_COEFFICIENT, MAX_COEFFICIENT);
+                }
+                if rng.gen_bool(config.modulator_mutation_probability) {
+                    plasticity.modulator = if rng.gen_bool(0.5) { Some(rng.gen_range(0..neurons)) } else { None };
+                }
+                plasticity.max_weight = config.max_weight;
+            }
+        }
+    }
+
+    /// Rule for a child laid out like this network. With two plastic parents every coefficient and the
+    /// modulator come from either one; with one plastic parent the child inherits its rule half of the time.
+    pub(crate) fn crossbreed_plasticity<R: Rng + ?Sized>(&self, other: &minet, rng: &mut R) -> Option<Plasticity> {
+        // The other parent's modulator is remapped onto this layout, and dropped if the neuron does not exist here
+        let theirs = other.plasticity.map(|plasticity| Plasticity {
+            modulator: plasticity.modulator.and_then(|neuron| other.counterpart_index(neuron, self)),
+            ..plasticity
+        });
+        match (self.plasticity, theirs) {
+            (Some(mut child), Some(mut theirs)) => {
+                for (mine, other) in child.rule.coefficients_mut().into_iter().zip(theirs.rule.coefficients_mut()) {
+                    if rng.gen_bool(0.5) {
+                        *mine = *other;
+                    }
+                }
+                if rng.gen_bool(0.5) {
+                    child.modulator = theirs.modulator;
+                }
+                Some(child)
+            }
+            (Some(plasticity), None) | (None, Some(plasticity)) => rng.gen_bool(0.5).then_some(plasticity),
+            (None, None) => None,
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use crate::{
+    minet,
+    minet_parsimony::{fittest, sort_indices_best_first},
+    EvolutionConfig, SpeciationConfig, Species,
+};
+use rand::{seq::SliceRandom, thread_rng, Rng};
+
+/// A speciated population of networks.
+/// Set each member's fitness, then call `evolve` to replace the members with the next generation.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Population {
+    pub members: Vec<minet>,
+    pub species: Vec<Species>,
+    pub config: EvolutionConfig,
+    pub speciation: SpeciationConfig,
+    pub generation: usize,
+    pub(crate) next_species_id: usize,
+}
+
+impl Population {
+    pub fn new(size: usize, input: usize, hidden: usize, output: usize, config: EvolutionConfig) -> Self {
+        Population::new_with_rng(size, input, hidden, output, config, &mut thread_rng())
+    }
+
+    pub fn new_with_rng<R: Rng + ?Sized>(
+        size: usize,
+        input: usize,
+        hidden: usize,
+        output: usize,
+        config: EvolutionConfig,
+        rng: &mut R,
+    ) -> Self {
+        let members = minet::initialize_population_with_rng(size, input, hidden, output, &config, rng);
+        Population::from_members(members, config)
+    }
+
+    /// Wraps existing networks, e.g. ones loaded from disk
+    pub fn from_members(members: Vec<minet>, config: EvolutionConfig) -> Self {
+        Population {
+            members,
+            species: Vec::new(),
+            config,
+            speciation: SpeciationConfig::default(),
+            generation: 0,
+            next_species_id: 0,
+        }
+    }
+
+    pub fn with_speciation(mut self, speciation: SpeciationConfig) -> Self {
+        self.speciation = speciation;
+        self
+    }
+
+    /// Returns the member with the highest fitness, see `EvolutionConfig::prefer_smaller_on_ties` for ties
+    pub fn best(&self) -> Option<&minet> {
+        fittest(&self.members, self.config.prefer_smaller_on_ties)
+    }
+
+    /// Sorts the members into species by distance to each species' representative.
+    /// Members that fit no species found a new one, and species left empty are dropped.
+    pub fn speciate(&mut self) {
+        for species in self.species.iter_mut() {
+            species.members.clear();
+        }
+
+        for (i, network) in self.members.iter().enumerate() {
+            let threshold = self.speciation.compatibility_threshold;
+            let compatible = self
+                .species
+                .iter_mut()
+                .find(|species| network.compatibility_distance(&species.representative, &self.speciation) < threshold);
+            match compatible {
+                Some(species) => species.members.push(i),
+                None => {
+                    let mut species = Species::new(self.next_species_id, network.clone(), self.generation);
+                    species.members.push(i);
+                    self.species.push(species);
+                    self.next_species_id += 1;
+                }
+            }
+        }
+        self.species.retain(|species| !species.members.is_empty());
+
+        if let Some(target) = self.speciation.target_species {
+            let step = self.speciation.threshold_step;
+            if self.species.len() > target {
+                self.speciation.compatibility_threshold += step;
+            } else if self.species.len() < target {
+                self.speciation.compatibility_threshold = (self.speciation.compatibility_threshold - step).max(step);
+            }
+        }
+    }
+
+    /// Explicit fitness sharing: each member's fitness divided by the size of its species.
+    /// Fitness is shifted so the worst member scores zero, keeping shares non-negative.
+    /// Call after `speciate`.
+    pub fn shared_fitness(&self) -> Vec<f32> {
+        let lowest = self.members.iter().map(|m| m.fitness).fold(f32::INFINITY, f32::min);
+        let mut shared = vec![0.0; self.members.len()];
+        for species in &self.species {
+            let size = species.members.len() as f32;
+            for &member in &species.members {
+                shared[member] = (self.members[member].fitness - lowest) / size;
+            }
+        }
+        shared
+    }
+
+    /// Offspring each species gets in a generation of `target` networks, proportional to its shared fitness
+    pub fn offspring_quotas(&self, target: usize) -> Vec<usize> {
+        let shared = self.shared_fitness();
+        let sums: Vec<f32> = self
+            .species
+            .iter()
+            .map(|species| species.members.iter().map(|&m| shared[m]).sum())
+            .collect();
+        let total: f32 = sums.iter().sum();
+
+        let exact: Vec<f32> = if total > 0.0 {
+            sums.iter().map(|sum| sum / total * target as f32).collect()
+        } else {
+            vec![target as f32 / self.species.len().max(1) as f32; self.species.len()]
+        };
+
+        // Hand out the floors, then the remainder to the largest fractional parts
+        let mut quotas: Vec<usize> = exact.iter().map(|q| q.floor() as usize).collect();
+        let mut order: Vec<usize> = (0..exact.len()).collect();
+        order.sort_by(|&a, &b| (exact[b] - exact[b].floor()).total_cmp(&(exact[a] - exact[a].floor())));
+        let assigned: usize = quotas.iter().sum();
+        for &i in order.iter().cycle().take(target.saturating_sub(assigned)) {
+            quotas[i] += 1;
+        }
+        quotas
+    }
+
+    /// Runs one generation with speciation: sorts members into species, culls stagnant species,
+    /// and breeds each species' quota of offspring from its fittest members.
+    /// The population keeps its current size.
+    pub fn evolve(&mut self) {
+        self.evolve_with_rng(&mut thread_rng());
+    }
+
+    pub fn evolve_with_rng<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let target = self.members.len();
+        if target == 0 {
+            return;
+        }
+
+        self.speciate();
+        self.update_stagnation();
+
+        let quotas = self.offspring_quotas(target);
+        let mut next = Vec::with_capacity(target);
+        for (species, &quota) in self.species.iter().zip(quotas.iter()) {
+            let mut ranked = species.members.clone();
+            sort_indices_best_first(&mut ranked, &self.members, self.config.prefer_smaller_on_ties);
+
+            let elites = self.speciation.species_elitism.min(quota).min(ranked.len());
+            for &elite in ranked.iter().take(elites) {
+                let mut champion = self.members[elite].clone();
+                champion.fitness = 0.0;
+                // Start the next evaluation fresh, like the bred children
+                champion.reset_state();
+                next.push(champion);
+            }
+
+            let survivors = ((ranked.len() as f32 * self.speciation.survival_rate).ceil() as usize).max(1);
+            ranked.truncate(survivors);
+            for _ in elites..quota {
+                let parent1 = &self.members[*ranked.choose(rng).unwrap()];
+                let parent2 = &self.members[*ranked.choose(rng).unwrap()];
+                next.push(parent1.crossbreed_with_rng(parent2, &self.config, rng));
+            }
+        }
+
+        // Next generation is compared against a random member of each species from this one
+        for species in self.species.iter_mut() {
+            if let Some(&member) = species.members.choose(rng) {
+                species.representative = self.members[member].clone();
+            }
+            species.members.clear();
+        }
+        self.members = next;
+        self.generation += 1;
+    }
+
+    /// Records species improvement and culls species that stagnated too long.
+    /// The species holding the overall best network is always kept.
+    fn update_stagnation(&mut self) {
+        let generation = self.generation;
+        for species in self.species.iter_mut() {
+            let best = species
+                .members
+                .iter()
+                .map(|&m| self.members[m].fitness)
+                .fold(f32::MIN, f32::max);
+            if best > species.best_fitness {
+                species.best_fitness = best;
+                species.last_improved = generation;
+            }
+        }
+
+        let best_species = self
+            .species
+            .iter()
+            .max_by(|a, b| a.best_fitness.total_cmp(&b.best_fitness))
+            .map(|species| species.id);
+        let limit = self.speciation.stagnation_limit;
+        self.species
+            .retain(|species| Some(species.id) == best_species || species.stagnation(generation) <= limit);
+    }
+}
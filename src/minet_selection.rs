@@ -0,0 +1,250 @@
+use crate::{
+    minet,
+    minet_parsimony::{fitness_order, sort_indices_best_first},
+    EvolutionConfig,
+};
+use rand::{distributions::WeightedIndex, prelude::Distribution, seq::SliceRandom, Rng, RngCore};
+
+/// Picks parents out of a population by fitness
+pub trait Selection {
+    /// Returns the index of one selected parent
+    fn select(&self, population: &[minet], rng: &mut dyn RngCore) -> usize;
+
+    /// Returns the indices of `count` selected parents
+    fn select_many(&self, population: &[minet], count: usize, rng: &mut dyn RngCore) -> Vec<usize> {
+        (0..count).map(|_| self.select(population, rng)).collect()
+    }
+}
+
+/// Uniform choice among the fittest `survival_rate` fraction, as in `crossbreed_population`
+#[derive(Clone, Debug)]
+pub struct Truncation {
+    pub survival_rate: f32,
+}
+
+impl Selection for Truncation {
+    fn select(&self, population: &[minet], rng: &mut dyn RngCore) -> usize {
+        self.select_many(population, 1, rng)[0]
+    }
+
+    fn select_many(&self, population: &[minet], count: usize, rng: &mut dyn RngCore) -> Vec<usize> {
+        let ranked = ranked_indices(population, false);
+        let survivors = ((population.len() as f32 * self.survival_rate).round() as usize).clamp(1, population.len());
+        (0..count).map(|_| *ranked[..survivors].choose(rng).unwrap()).collect()
+    }
+}
+
+/// Best of `size` uniformly drawn contestants
+#[derive(Clone, Debug)]
+pub struct Tournament {
+    pub size: usize,
+}
+
+impl Selection for Tournament {
+    fn select(&self, population: &[minet], rng: &mut dyn RngCore) -> usize {
+        (0..self.size.max(1))
+            .map(|_| rng.gen_range(0..population.len()))
+            .max_by(|&a, &b| fitness_order(population[a].fitness, population[b].fitness))
+            .unwrap()
+    }
+}
+
+/// Fitness-proportional roulette wheel.
+/// Fitness is shifted so the worst network has zero chance unless every network is equal.
+#[derive(Clone, Debug)]
+pub struct RouletteWheel;
+
+impl Selection for RouletteWheel {
+    fn select(&self, population: &[minet], rng: &mut dyn RngCore) -> usize {
+        self.select_many(population, 1, rng)[0]
+    }
+
+    fn select_many(&self, population: &[minet], count: usize, rng: &mut dyn RngCore) -> Vec<usize> {
+        let weights = proportional_weights(population);
+        (0..count).map(|_| weighted_choice(&weights, rng)).collect()
+    }
+}
+
+/// Fitness-proportional selection with evenly spaced pointers, which keeps the number of
+/// picks per network close to its expected share
+#[derive(Clone, Debug)]
+pub struct StochasticUniversalSampling;
+
+impl Selection for StochasticUniversalSampling {
+    fn select(&self, population: &[minet], rng: &mut dyn RngCore) -> usize {
+        self.select_many(population, 1, rng)[0]
+    }
+
+    fn select_many(&self, population: &[minet], count: usize, rng: &mut dyn RngCore) -> Vec<usize> {
+        if count == 0 {
+            return Vec::new();
+        }
+        let weights = proportional_weights(population);
+        let total: f32 = weights.iter().sum();
+        if total <= 0.0 {
+            return (0..count).map(|_| rng.gen_range(0..population.len())).collect();
+        }
+
+        let spacing = total / count as f32;
+        let mut pointer = rng.gen_range(0.0..spacing);
+        let mut selected = Vec::with_capacity(count);
+        let mut cumulative = 0.0;
+        for (i, weight) in weights.iter().enumerate() {
+            cumulative += weight;
+            while pointer < cumulative && selected.len() < count {
+                selected.push(i);
+                pointer += spacing;
+            }
+        }
+        // Float rounding can leave the last pointer just past the end
+        while selected.len() < count {
+            selected.push(population.len() - 1);
+        }
+
+        // Pointers come out in population order, shuffle so consecutive picks pair up randomly
+        selected.shuffle(rng);
+        selected
+    }
+}
+
+/// Linear ranking: the chance of selection depends only on rank.
+/// `selection_pressure` in [1, 2] is the expected number of picks of the best network per pick of the average one.
+#[derive(Clone, Debug)]
+pub struct RankBased {
+    pub selection_pressure: f32,
+}
+
+impl Selection for RankBased {
+    fn select(&self, population: &[minet], rng: &mut dyn RngCore) -> usize {
+        self.select_many(population, 1, rng)[0]
+    }
+
+    fn select_many(&self, population: &[minet], count: usize, rng: &mut dyn RngCore) -> Vec<usize> {
+        let ranked = ranked_indices(population, false);
+        let n = population.len();
+        let pressure = self.selection_pressure.clamp(1.0, 2.0);
+        let weights: Vec<f32> = (0..n)
+            .map(|rank| {
+                // rank 0 is the best
+                let position = if n > 1 { (n - 1 - rank) as f32 / (n - 1) as f32 } else { 1.0 };
+                (2.0 - pressure) + 2.0 * (pressure - 1.0) * position
+            })
+            .collect();
+        (0..count).map(|_| ranked[weighted_choice(&weights, rng)]).collect()
+    }
+}
+
+/// Chance proportional to exp(fitness / temperature).
+/// Low temperatures approach greedy selection, high ones approach uniform.
+#[derive(Clone, Debug)]
+pub struct Boltzmann {
+    pub temperature: f32,
+}
+
+impl Selection for Boltzmann {
+    fn select(&self, population: &[minet], rng: &mut dyn RngCore) -> usize {
+        self.select_many(population, 1, rng)[0]
+    }
+
+    fn select_many(&self, population: &[minet], count: usize, rng: &mut dyn RngCore) -> Vec<usize> {
+        let best = population.iter().map(|m| m.fitness).fold(f32::MIN, f32::max);
+        let temperature = self.temperature.max(f32::EPSILON);
+        let weights: Vec<f32> = population
+            .iter()
+            .map(|m| ((m.fitness - best) / temperature).exp())
+            .collect();
+        (0..count).map(|_| weighted_choice(&weights, rng)).collect()
+    }
+}
+
+/// Filters the population through the test cases in random order, keeping only the networks
+/// within `epsilon` of the best score on each case.
+/// `cases[i][c]` is network i's score on case c, higher is better.
+#[derive(Clone, Debug)]
+pub struct Lexicase {
+    pub cases: Vec<Vec<f32>>,
+    pub epsilon: f32,
+}
+
+impl Selection for Lexicase {
+    fn select(&self, population: &[minet], rng: &mut dyn RngCore) -> usize {
+        let mut candidates: Vec<usize> = (0..population.len().min(self.cases.len())).collect();
+        if candidates.is_empty() {
+            return rng.gen_range(0..population.len());
+        }
+
+        let case_count = self.cases.iter().map(|c| c.len()).min().unwrap_or(0);
+        let mut order: Vec<usize> = (0..case_count).collect();
+        order.shuffle(rng);
+
+        for case in order {
+            let best = candidates
+                .iter()
+                .map(|&i| self.cases[i][case])
+                .fold(f32::MIN, f32::max);
+            candidates.retain(|&i| self.cases[i][case] >= best - self.epsilon);
+            if candidates.len() == 1 {
+                break;
+            }
+        }
+        *candidates.choose(rng).unwrap()
+    }
+}
+
+/// Population indices sorted from best to worst fitness, see `EvolutionConfig::prefer_smaller_on_ties`
+fn ranked_indices(population: &[minet], prefer_smaller: bool) -> Vec<usize> {
+    let mut ranked: Vec<usize> = (0..population.len()).collect();
+    sort_indices_best_first(&mut ranked, population, prefer_smaller);
+    ranked
+}
+
+fn proportional_weights(population: &[minet]) -> Vec<f32> {
+    let lowest = population.iter().map(|m| m.fitness).fold(f32::INFINITY, f32::min);
+    population.iter().map(|m| m.fitness - lowest).collect()
+}
+
+/// Weighted index, falling back to a uniform choice when every weight is zero
+fn weighted_choice(weights: &[f32], rng: &mut dyn RngCore) -> usize {
+    match WeightedIndex::new(weights) {
+        Ok(distribution) => distribution.sample(rng),
+        Err(_) => rng.gen_range(0..weights.len()),
+    }
+}
+
+impl minet {
+    /// Builds a new generation of `target_population` networks.
+    /// The `elitism` fittest networks are copied over unmutated with their stored state cleared,
+    /// every other child is bred from two parents picked by `selection`.
+    pub fn crossbreed_population_with_selection<R: Rng + ?Sized>(
+        population: &[minet],
+        selection: &dyn Selection,
+        elitism: usize,
+        target_population: usize,
+        config: &EvolutionConfig,
+        mut rng: &mut R,
+    ) -> Vec<minet> {
+        if population.is_empty() {
+            return Vec::new();
+        }
+
+        let elites = elitism.min(population.len()).min(target_population);
+        let mut next: Vec<minet> = ranked_indices(population, config.prefer_smaller_on_ties)
+            .into_iter()
+            .take(elites)
+            .map(|i| {
+                let mut elite = population[i].clone();
+                elite.fitness = 0.0;
+                elite.reset_state();
+                elite
+            })
+            .collect();
+
+        let children = target_population - elites;
+        let parents = selection.select_many(population, children * 2, &mut rng);
+        for pair in parents.chunks(2) {
+            let child = population[pair[0]].crossbreed_with_rng(&population[pair[1]], config, rng);
+            next.push(child);
+        }
+        next
+    }
+}
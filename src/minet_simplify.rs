@@ -0,0 +1,129 @@
+use crate::minet;
+
+/// What `simplify` removed from a network
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SimplifyReport {
+    /// Indices, before simplification, of the hidden neurons that were removed
+    pub removed_neurons: Vec<usize>,
+    /// How many of `removed_neurons` had a constant activation that was folded into the biases of their targets
+    pub folded_neurons: usize,
+    /// Synapses removed because their weight was exactly zero
+    pub zero_weight_synapses: usize,
+    /// Synapses removed along with the neurons they touched
+    pub dangling_synapses: usize,
+}
+
+impl SimplifyReport {
+    /// Returns true if nothing was removed
+    pub fn is_empty(&self) -> bool {
+        self.removed_neurons.is_empty() && self.zero_weight_synapses == 0 && self.dangling_synapses == 0
+    }
+}
+
+impl minet {
+    /// Removes structure that cannot change the outputs:
+    /// 1. Synapses with a weight of exactly zero
+    /// 2. Hidden neurons with no path to an output, with all of their synapses
+    /// 3. Hidden neurons with no incoming synapses and only forward outgoing ones. Their activation is
+    ///    the same on every pass, so it is added to the bias of each target, scaled by the synapse weight.
+    ///
+    /// Inputs and outputs are never removed. Outputs are unchanged apart from float rounding in folded biases.
+    /// Hidden neurons that only feed themselves through recurrent synapses are kept, since their activation
+    /// changes from tick to tick.
+    ///
+    /// Plastic weights can move away from zero and constant neurons stop being constant once their
+    /// synapses learn, so plastic networks only lose neurons without a path to an output.
+    /// Their neuromodulator is always kept.
+    ///
+    /// In continuous genomes a constant neuron still feeds its targets through their time constants
+    /// when run by `Ctrnn`, so it is never folded into their biases.
+    pub fn simplify(&mut self) -> SimplifyReport {
+        let mut report = SimplifyReport::default();
+        // original[i] is the index neuron i had before simplification
+        let mut original: Vec<usize> = (0..self.genes.len()).collect();
+        let plastic = self.is_plastic();
+        let foldable = !plastic && !self.is_continuous();
+
+        if !plastic {
+            for gene in self.genes.iter_mut() {
+                let before = gene.1.len();
+                gene.1.retain(|&(_, weight)| weight != 0.0);
+                report.zero_weight_synapses += before - gene.1.len();
+            }
+        }
+
+        let reaches_output = self.neurons_reaching_output();
+        for index in (self.input..self.input + self.hidden).rev() {
+            if !reaches_output[index] {
+                report.dangling_synapses += self.neuron_synapse_count(index);
+                report.removed_neurons.push(original.remove(index));
+                self.neuron_remove(index);
+            }
+        }
+
+        // Folding a neuron can leave its targets without inputs, so repeat until nothing changes
+        while let Some(index) = (self.input..self.input + self.hidden).find(|&i| foldable && self.neuron_is_constant(i)) {
+            let (bias, synapses, activation) = self.genes[index].clone();
+            let value = activation.apply(bias);
+            for (target, weight) in synapses {
+                self.genes[target].0 += value * weight;
+            }
+            report.dangling_synapses += self.neuron_synapse_count(index);
+            report.removed_neurons.push(original.remove(index));
+            report.folded_neurons += 1;
+            self.neuron_remove(index);
+        }
+
+        report.removed_neurons.sort_unstable();
+        report
+    }
+
+    /// Returns (active neurons, active synapses): the hidden and output neurons and the synapses
+    /// left after `simplify`. Useful as a complexity penalty in a fitness function.
+    pub fn effective_size(&self) -> (usize, usize) {
+        let mut simplified = self.clone();
+        simplified.simplify();
+        (simplified.hidden + simplified.output, simplified.synapse_count())
+    }
+
+    /// Marks every neuron with a path to an output or the plasticity modulator, following forward and recurrent synapses
+    fn neurons_reaching_output(&self) -> Vec<bool> {
+        let length = self.genes.len();
+        let mut incoming: Vec<Vec<usize>> = vec![Vec::new(); length];
+        for (source, gene) in self.genes.iter().enumerate() {
+            for &(target, _) in &gene.1 {
+                incoming[target].push(source);
+            }
+        }
+
+        let mut reaches = vec![false; length];
+        let mut stack: Vec<usize> = (length - self.output..length).collect();
+        // The modulator shapes every plastic weight, so whatever feeds it matters too
+        stack.extend(self.plasticity.and_then(|plasticity| plasticity.modulator));
+        while let Some(neuron) = stack.pop() {
+            if !reaches[neuron] {
+                reaches[neuron] = true;
+                stack.extend(incoming[neuron].iter().copied().filter(|&source| !reaches[source]));
+            }
+        }
+        reaches
+    }
+
+    /// A hidden neuron fed by nothing, whose synapses all go forward, has the same activation on every pass
+    fn neuron_is_constant(&self, index: usize) -> bool {
+        let fed = self.genes.iter().any(|gene| gene.1.iter().any(|&(target, _)| target == index));
+        !fed && self.genes[index].1.iter().all(|&(target, _)| target > index)
+    }
+
+    /// Synapses into or out of a neuron, counting a self-loop once
+    fn neuron_synapse_count(&self, index: usize) -> usize {
+        let incoming = self
+            .genes
+            .iter()
+            .enumerate()
+            .filter(|&(source, _)| source != index)
+            .map(|(_, gene)| gene.1.iter().filter(|&&(target, _)| target == index).count())
+            .sum::<usize>();
+        incoming + self.genes[index].1.len()
+    }
+}
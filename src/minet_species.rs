@@ -0,0 +1,151 @@
+use crate::minet;
+
+/// Parameters for grouping genomes into species by compatibility distance
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpeciationConfig {
+    /// Genomes closer than this to a species' representative join that species
+    pub compatibility_threshold: f32,
+    /// Weight of synapses touching hidden neurons only one genome has
+    pub excess_coefficient: f32,
+    /// Weight of the remaining synapses only one genome has
+    pub disjoint_coefficient: f32,
+    /// Weight of the mean weight difference of matching synapses
+    pub weight_coefficient: f32,
+    /// Weight of the mean bias difference of matching neurons
+    pub bias_coefficient: f32,
+    /// Weight of the fraction of matching neurons with different activations
+    pub activation_coefficient: f32,
+    /// When set, the threshold is nudged each generation towards this many species
+    pub target_species: Option<usize>,
+    /// How far the threshold moves per generation when chasing `target_species`
+    pub threshold_step: f32,
+    /// Species whose best fitness has not improved for this many generations are culled
+    pub stagnation_limit: usize,
+    /// Fraction of each species, by fitness, allowed to parent offspring
+    pub survival_rate: f32,
+    /// Champions of each species copied unmutated into the next generation, with their stored state cleared
+    pub species_elitism: usize,
+}
+
+impl Default for SpeciationConfig {
+    fn default() -> Self {
+        SpeciationConfig {
+            compatibility_threshold: 3.0,
+            excess_coefficient: 1.0,
+            disjoint_coefficient: 1.0,
+            weight_coefficient: 0.4,
+            bias_coefficient: 0.4,
+            activation_coefficient: 0.5,
+            target_species: None,
+            threshold_step: 0.1,
+            stagnation_limit: 15,
+            survival_rate: 0.2,
+            species_elitism: 1,
+        }
+    }
+}
+
+/// A group of mutually compatible genomes that compete mostly among themselves
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Species {
+    pub id: usize,
+    /// Genome new members are compared against, picked from the previous generation
+    pub representative: minet,
+    /// Indices into the population's members
+    pub members: Vec<usize>,
+    /// Best raw fitness the species has reached
+    pub best_fitness: f32,
+    /// Generation in which `best_fitness` last improved
+    pub last_improved: usize,
+}
+
+impl Species {
+    pub fn new(id: usize, representative: minet, generation: usize) -> Self {
+        Species {
+            id,
+            representative,
+            members: Vec::new(),
+            best_fitness: f32::MIN,
+            last_improved: generation,
+        }
+    }
+
+    /// Generations since the species last improved
+    pub fn stagnation(&self, generation: usize) -> usize {
+        generation.saturating_sub(self.last_improved)
+    }
+}
+
+impl minet {
+    /// Measures how different two genomes are.
+    /// Neurons are matched with `counterpart_index`, synapses by their matched (source, target) pair.
+    /// Excess synapses touch a hidden neuron only one genome has, disjoint synapses are the other unmatched ones.
+    pub fn compatibility_distance(&self, other: &minet, config: &SpeciationConfig) -> f32 {
+        let mut excess = 0;
+        let mut disjoint = 0;
+        let mut matching = 0;
+        let mut weight_difference = 0.0;
+
+        for (source, gene) in self.genes.iter().enumerate() {
+            for &(target, weight) in &gene.1 {
+                match (self.counterpart_index(source, other), self.counterpart_index(target, other)) {
+                    (Some(other_source), Some(other_target)) => {
+                        match other.genes[other_source].1.iter().find(|&&(t, _)| t == other_target) {
+                            Some(&(_, other_weight)) => {
+                                matching += 1;
+                                weight_difference += (weight - other_weight).abs();
+                            }
+                            None => disjoint += 1,
+                        }
+                    }
+                    _ => excess += 1,
+                }
+            }
+        }
+
+        for (source, gene) in other.genes.iter().enumerate() {
+            for &(target, _) in &gene.1 {
+                match (other.counterpart_index(source, self), other.counterpart_index(target, self)) {
+                    (Some(self_source), Some(self_target)) => {
+                        if !self.synapse_is_connected(self_source, self_target) {
+                            disjoint += 1;
+                        }
+                    }
+                    _ => excess += 1,
+                }
+            }
+        }
+
+        let mut matching_neurons = 0;
+        let mut bias_difference = 0.0;
+        let mut activation_difference = 0;
+        for (i, gene) in self.genes.iter().enumerate() {
+            if let Some(j) = self.counterpart_index(i, other) {
+                matching_neurons += 1;
+                bias_difference += (gene.0 - other.genes[j].0).abs();
+                if gene.2 != other.genes[j].2 {
+                    activation_difference += 1;
+                }
+            }
+        }
+
+        let size = self.synapse_count().max(other.synapse_count()).max(1) as f32;
+        let mean_weight_difference = if matching > 0 { weight_difference / matching as f32 } else { 0.0 };
+        let (mean_bias_difference, activation_fraction) = if matching_neurons > 0 {
+            (
+                bias_difference / matching_neurons as f32,
+                activation_difference as f32 / matching_neurons as f32,
+            )
+        } else {
+            (0.0, 0.0)
+        };
+
+        config.excess_coefficient * excess as f32 / size
+            + config.disjoint_coefficient * disjoint as f32 / size
+            + config.weight_coefficient * mean_weight_difference
+            + config.bias_coefficient * mean_bias_difference
+            + config.activation_coefficient * activation_fraction
+    }
+}
@@ -0,0 +1,287 @@
+use crate::minet;
+use rand::{seq::SliceRandom, thread_rng, Rng};
+
+/// Gradients of a loss with respect to every bias and synapse weight, shaped like the genome:
+/// `weights[i][j]` belongs to synapse j of neuron i
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gradients {
+    pub biases: Vec<f32>,
+    pub weights: Vec<Vec<f32>>,
+}
+
+impl Gradients {
+    /// All-zero gradients for a network
+    pub fn zeros(network: &minet) -> Self {
+        Gradients {
+            biases: vec![0.0; network.genes.len()],
+            weights: network.genes.iter().map(|gene| vec![0.0; gene.1.len()]).collect(),
+        }
+    }
+
+    /// The gradients in `minet::flatten` order for the network they were computed on
+    pub fn flatten(&self, network: &minet) -> Vec<f32> {
+        let mut flat = Vec::with_capacity(network.parameter_count());
+        for (i, (bias, weights)) in self.biases.iter().zip(&self.weights).enumerate() {
+            if i >= network.input {
+                flat.push(*bias);
+            }
+            flat.extend_from_slice(weights);
+        }
+        flat
+    }
+
+    /// Adds `scale * other` to these gradients
+    pub fn add_scaled(&mut self, other: &Gradients, scale: f32) {
+        for (bias, other) in self.biases.iter_mut().zip(&other.biases) {
+            *bias += scale * other;
+        }
+        for (weights, other) in self.weights.iter_mut().zip(&other.weights) {
+            for (weight, other) in weights.iter_mut().zip(other) {
+                *weight += scale * other;
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Loss {
+    /// Mean over outputs of (output - target)^2
+    MeanSquaredError,
+    /// Mean over outputs of binary cross-entropy, for outputs in (0, 1) such as sigmoid outputs
+    BinaryCrossEntropy,
+}
+
+impl Loss {
+    /// Loss of one sample and its gradient with respect to each output
+    pub fn evaluate(self, outputs: &[f32], targets: &[f32]) -> (f32, Vec<f32>) {
+        let count = outputs.len().max(1) as f32;
+        let mut loss = 0.0;
+        let gradient = outputs
+            .iter()
+            .zip(targets)
+            .map(|(&output, &target)| match self {
+                Loss::MeanSquaredError => {
+                    let error = output - target;
+                    loss += error * error / count;
+                    2.0 * error / count
+                }
+                Loss::BinaryCrossEntropy => {
+                    let output = output.clamp(1e-7, 1.0 - 1e-7);
+                    loss -= (target * output.ln() + (1.0 - target) * (1.0 - output).ln()) / count;
+                    (output - target) / (output * (1.0 - output)) / count
+                }
+            })
+            .collect();
+        (loss, gradient)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Optimizer {
+    /// Plain gradient descent with optional momentum
+    Sgd { learning_rate: f32, momentum: f32 },
+    Adam { learning_rate: f32, beta1: f32, beta2: f32, epsilon: f32 },
+}
+
+impl Optimizer {
+    pub fn adam(learning_rate: f32) -> Self {
+        Optimizer::Adam { learning_rate, beta1: 0.9, beta2: 0.999, epsilon: 1e-8 }
+    }
+}
+
+/// Settings for `train_supervised`
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrainingConfig {
+    pub optimizer: Optimizer,
+    pub loss: Loss,
+    /// Passes over the whole dataset
+    pub epochs: usize,
+    /// Samples averaged per optimizer step, the dataset is shuffled every epoch
+    pub batch_size: usize,
+    /// Weights are clamped to [-max_weight, max_weight] after every step, like `EvolutionConfig::max_weight`
+    pub max_weight: f32,
+    /// Biases are clamped to [-max_bias, max_bias] after every step
+    pub max_bias: f32,
+}
+
+impl Default for TrainingConfig {
+    fn default() -> Self {
+        TrainingConfig {
+            optimizer: Optimizer::adam(0.01),
+            loss: Loss::MeanSquaredError,
+            epochs: 100,
+            batch_size: 16,
+            max_weight: 10.0,
+            max_bias: 10.0,
+        }
+    }
+}
+
+impl minet {
+    /// Gradients of a loss with respect to every bias and weight for one sample, by reverse-mode
+    /// differentiation through the network. `output_gradient` is the derivative of the loss with respect to each output.
+    /// Recurrent synapses read the stored `state`, which is treated as a constant:
+    /// their weights get gradients, but nothing flows back into earlier passes.
+    /// Plastic networks are differentiated at their current `weight_changes`, which are held constant too.
+    pub fn backward(&self, inputs: &[f32], output_gradient: &[f32]) -> Gradients {
+        let (pre_activation, activation_map) = self.forward_trace(inputs);
+        let length = self.genes.len();
+        let output_start = length - self.output;
+
+        let mut gradients = Gradients::zeros(self);
+        // Derivative of the loss with respect to each neuron's pre-activation sum
+        let mut delta = vec![0.0; length];
+
+        // Every forward synapse points to a higher index, so walking down visits each target before its sources
+        for source in (0..length).rev() {
+            let mut upstream = if source >= output_start {
+                output_gradient.get(source - output_start).copied().unwrap_or(0.0)
+            } else {
+                0.0
+            };
+            for (j, (target, weight)) in self.lifetime_synapses(source).enumerate() {
+                if target > source {
+                    upstream += weight * delta[target];
+                    gradients.weights[source][j] = activation_map[source] * delta[target];
+                }
+            }
+            if source >= self.input {
+                let (_, _, activation) = self.genes[source];
+                delta[source] = upstream * activation.derivative(pre_activation[source], activation_map[source]);
+                gradients.biases[source] = delta[source];
+            }
+        }
+
+        for (source, gene) in self.genes.iter().enumerate() {
+            let previous = self.state.get(source).copied().unwrap_or(0.0);
+            for (j, &(target, _)) in gene.1.iter().enumerate() {
+                if target <= source {
+                    gradients.weights[source][j] = previous * delta[target];
+                }
+            }
+        }
+        gradients
+    }
+
+    /// Fits weights and biases to a dataset by gradient descent and returns the mean loss of the last epoch.
+    /// The topology is left alone. Train the network itself for Lamarckian evolution, where learned weights
+    /// are inherited, or a clone whose trained fitness scores the original for Baldwinian evolution.
+    pub fn train_supervised(&mut self, inputs: &[Vec<f32>], targets: &[Vec<f32>], config: &TrainingConfig) -> f32 {
+        self.train_supervised_with_rng(inputs, targets, config, &mut thread_rng())
+    }
+
+    pub fn train_supervised_with_rng<R: Rng + ?Sized>(
+        &mut self,
+        inputs: &[Vec<f32>],
+        targets: &[Vec<f32>],
+        config: &TrainingConfig,
+        rng: &mut R,
+    ) -> f32 {
+        let samples = inputs.len().min(targets.len());
+        if samples == 0 {
+            return 0.0;
+        }
+
+        let mut optimizer = OptimizerState::new(config.optimizer, self.parameter_count());
+        let mut order: Vec<usize> = (0..samples).collect();
+        let mut epoch_loss = 0.0;
+        for _ in 0..config.epochs {
+            order.shuffle(rng);
+            epoch_loss = 0.0;
+            for batch in order.chunks(config.batch_size.max(1)) {
+                let mut total = Gradients::zeros(self);
+                for &sample in batch {
+                    let outputs = self.forward(inputs[sample].clone());
+                    let (loss, output_gradient) = config.loss.evaluate(&outputs, &targets[sample]);
+                    epoch_loss += loss;
+                    total.add_scaled(&self.backward(&inputs[sample], &output_gradient), 1.0 / batch.len() as f32);
+                }
+                let mut parameters = self.flatten();
+                optimizer.step(&mut parameters, &total.flatten(self));
+                self.unflatten(&parameters);
+                self.clamp_parameters(config.max_weight, config.max_bias);
+            }
+            epoch_loss /= samples as f32;
+        }
+        epoch_loss
+    }
+
+    /// Forward pass that also returns each neuron's pre-activation sum, mirroring `forward_display`
+    fn forward_trace(&self, inputs: &[f32]) -> (Vec<f32>, Vec<f32>) {
+        let length = self.genes.len();
+        let mut sums = vec![0.0; length];
+        let mut activation_map = vec![0.0; length];
+
+        for source in 0..length {
+            let previous = self.state.get(source).copied().unwrap_or(0.0);
+            for (target, weight) in self.lifetime_synapses(source) {
+                if target <= source {
+                    sums[target] += previous * weight;
+                }
+            }
+        }
+
+        for (i, &input) in inputs.iter().enumerate().take(self.input) {
+            activation_map[i] = input;
+            for (target, weight) in self.lifetime_synapses(i) {
+                sums[target] += input * weight;
+            }
+        }
+
+        for (i, (bias, _, activation)) in self.genes.iter().enumerate().skip(self.input) {
+            sums[i] += bias;
+            let value = activation.apply(sums[i]);
+            activation_map[i] = value;
+            for (target, weight) in self.lifetime_synapses(i) {
+                if target > i {
+                    sums[target] += value * weight;
+                }
+            }
+        }
+        (sums, activation_map)
+    }
+}
+
+/// Per-parameter optimizer memory over `minet::flatten` vectors
+pub(crate) struct OptimizerState {
+    optimizer: Optimizer,
+    first_moment: Vec<f32>,
+    second_moment: Vec<f32>,
+    steps: i32,
+}
+
+impl OptimizerState {
+    pub(crate) fn new(optimizer: Optimizer, parameters: usize) -> Self {
+        OptimizerState {
+            optimizer,
+            first_moment: vec![0.0; parameters],
+            second_moment: vec![0.0; parameters],
+            steps: 0,
+        }
+    }
+
+    /// Moves `parameters` one step against `gradient`
+    pub(crate) fn step(&mut self, parameters: &mut [f32], gradient: &[f32]) {
+        self.steps += 1;
+        for (i, (parameter, &gradient)) in parameters.iter_mut().zip(gradient).enumerate() {
+            let first = &mut self.first_moment[i];
+            match self.optimizer {
+                Optimizer::Sgd { learning_rate, momentum } => {
+                    *first = momentum * *first + gradient;
+                    *parameter -= learning_rate * *first;
+                }
+                Optimizer::Adam { learning_rate, beta1, beta2, epsilon } => {
+                    let second = &mut self.second_moment[i];
+                    *first = beta1 * *first + (1.0 - beta1) * gradient;
+                    *second = beta2 * *second + (1.0 - beta2) * gradient * gradient;
+                    let first_corrected = *first / (1.0 - beta1.powi(self.steps));
+                    let second_corrected = *second / (1.0 - beta2.powi(self.steps));
+                    *parameter -= learning_rate * first_corrected / (second_corrected.sqrt() + epsilon);
+                }
+            }
+        }
+    }
+}
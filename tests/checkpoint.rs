@@ -0,0 +1,81 @@
+use minet_ai::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::path::PathBuf;
+
+/// Rewards remembering the previous input, so recurrent networks score higher
+fn fitness(network: &mut minet) -> f32 {
+    let sequence = [1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 0.0];
+    let mut error = 0.0;
+    for pair in sequence.windows(2) {
+        let output = network.forward_stateful(vec![pair[1], 1.0]);
+        error += (output[0] - pair[0]).abs();
+    }
+    -error
+}
+
+fn population() -> Population {
+    // Values away from the defaults, so a field lost on the way through the checkpoint changes the run
+    let config = EvolutionConfig {
+        initial_weight_std_dev: 0.7,
+        weight_std_dev: 0.2,
+        bias_std_dev: 0.05,
+        weight_mutation_probability: 0.8,
+        weight_replace_probability: 0.05,
+        synapse_swap_probability: 0.3,
+        add_synapse_probability: 0.3,
+        remove_synapse_probability: 0.1,
+        add_recurrent_synapse_probability: 0.2,
+        allow_self_loops: false,
+        add_neuron_probability: 0.1,
+        remove_neuron_probability: 0.05,
+        max_hidden: 8,
+        activation_mutation_probability: 0.05,
+        initial_synapses_per_neuron: 2,
+        max_weight: 4.0,
+        max_bias: 3.0,
+        ..Default::default()
+    };
+    let speciation = SpeciationConfig {
+        compatibility_threshold: 1.5,
+        target_species: Some(4),
+        threshold_step: 0.2,
+        stagnation_limit: 6,
+        survival_rate: 0.3,
+        species_elitism: 2,
+        ..Default::default()
+    };
+    Population::new_with_rng(40, 2, 4, 1, config, &mut ChaCha8Rng::seed_from_u64(5)).with_speciation(speciation)
+}
+
+fn evolver<'a>(evolver: Evolver<'a>) -> Evolver<'a> {
+    evolver.breeding(Breeding::Speciated).hall_of_fame_size(5).max_generations(20)
+}
+
+fn encoded(networks: &[minet]) -> Vec<(String, u32)> {
+    networks.iter().map(|network| (network.encode(), network.fitness.to_bits())).collect()
+}
+
+#[test]
+fn resumed_run_matches_an_uninterrupted_one() {
+    let path: PathBuf = std::env::temp_dir().join(format!("minet_resume_{}.ckpt", std::process::id()));
+
+    let uninterrupted = evolver(Evolver::new(population(), fitness).seed(9)).run();
+
+    // Checkpoints at generations 5 and 10, then keeps going as if it crashed after 12
+    let interrupted = evolver(Evolver::new(population(), fitness).seed(9))
+        .checkpoint_every(5, &path)
+        .max_generations(12)
+        .run();
+    assert!(interrupted.checkpoint_error.is_none());
+    let resumed = evolver(Evolver::resume(&path, fitness).unwrap()).run();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(resumed.population.generation, uninterrupted.population.generation);
+    assert_eq!(encoded(&resumed.population.members), encoded(&uninterrupted.population.members));
+    assert_eq!(encoded(&resumed.hall_of_fame), encoded(&uninterrupted.hall_of_fame));
+    assert_eq!(resumed.population.speciation.compatibility_threshold, uninterrupted.population.speciation.compatibility_threshold);
+
+    let best = |history: &[GenerationStats]| -> Vec<u32> { history.iter().map(|stats| stats.best_fitness.to_bits()).collect() };
+    assert_eq!(best(&resumed.stats.history), best(&uninterrupted.stats.history[10..]));
+}
@@ -0,0 +1,44 @@
+use minet_ai::*;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// Networks grown with recurrent synapses and new neurons, with every hidden and
+/// output neuron given a random activation and one hidden neuron cut off from the outputs
+pub fn evolved_networks(seed: u64) -> Vec<minet> {
+    let config = EvolutionConfig {
+        add_synapse_probability: 0.5,
+        add_recurrent_synapse_probability: 0.5,
+        add_neuron_probability: 0.2,
+        ..Default::default()
+    };
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut population = minet::initialize_population_with_rng(20, 3, 6, 2, &config, &mut rng);
+    for _ in 0..10 {
+        for network in population.iter_mut() {
+            network.fitness = rng.gen();
+        }
+        population = minet::crossbreed_population_with_rng(population, 0.5, 20, &config, &mut rng);
+    }
+
+    for network in population.iter_mut() {
+        for gene in network.genes.iter_mut().skip(network.input) {
+            gene.2 = Activation::ALL[rng.gen_range(0..Activation::ALL.len())];
+        }
+        // The first hidden neuron still computes but can no longer reach an output
+        let dangling = network.input;
+        network.genes[dangling].1.clear();
+        network.genes[0].1.push((dangling, 0.7));
+    }
+    population
+}
+
+/// Input of the given step of a sequence
+pub fn inputs(step: usize) -> Vec<f32> {
+    let t = step as f32;
+    vec![(0.7 * t).sin(), (0.3 * t).cos(), 0.1 * t - 0.5]
+}
+
+/// Bit patterns, so equality means identical floats and not merely close ones
+pub fn bits(values: &[f32]) -> Vec<u32> {
+    values.iter().map(|value| value.to_bits()).collect()
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use common::{bits, evolved_networks, inputs};
+use minet_ai::{HebbianRule, Plasticity};
+
+const STEPS: usize = 12;
+
+#[test]
+fn evolved_networks_have_recurrence_and_pruned_neurons() {
+    let networks = evolved_networks(1);
+    for network in &networks {
+        assert!(network.compile().neuron_count() < network.genes.len());
+    }
+    let recurrent = networks
+        .iter()
+        .filter(|network| {
+            network.genes.iter().enumerate().any(|(source, gene)| gene.1.iter().any(|&(target, _)| target <= source))
+        })
+        .count();
+    assert!(recurrent > 0, "no evolved network has a recurrent synapse");
+}
+
+#[test]
+fn forward_into_matches_forward() {
+    for (i, network) in evolved_networks(2).iter().enumerate() {
+        let mut compiled = network.compile();
+        let mut outputs = vec![0.0; compiled.output_count()];
+        for step in 0..STEPS {
+            compiled.forward_into(&inputs(step), &mut outputs);
+            assert_eq!(bits(&network.forward(inputs(step))), bits(&outputs), "network {i}, step {step}");
+        }
+    }
+}
+
+#[test]
+fn forward_stateful_into_matches_forward_stateful() {
+    for (i, mut network) in evolved_networks(3).into_iter().enumerate() {
+        let mut compiled = network.compile();
+        let mut outputs = vec![0.0; compiled.output_count()];
+        for step in 0..STEPS {
+            let expected = network.forward_stateful(inputs(step));
+            compiled.forward_stateful_into(&inputs(step), &mut outputs);
+            assert_eq!(bits(&expected), bits(&outputs), "network {i}, step {step}");
+        }
+    }
+}
+
+#[test]
+fn compiling_carries_over_a_warmed_up_state() {
+    for (i, mut network) in evolved_networks(4).into_iter().enumerate() {
+        for step in 0..STEPS {
+            network.forward_stateful(inputs(step));
+        }
+        let mut compiled = network.compile();
+        let mut outputs = vec![0.0; compiled.output_count()];
+
+        compiled.forward_into(&inputs(0), &mut outputs);
+        assert_eq!(bits(&network.forward(inputs(0))), bits(&outputs), "network {i}, stateless");
+
+        for step in STEPS..2 * STEPS {
+            let expected = network.forward_stateful(inputs(step));
+            compiled.forward_stateful_into(&inputs(step), &mut outputs);
+            assert_eq!(bits(&expected), bits(&outputs), "network {i}, step {step}");
+        }
+    }
+}
+
+#[test]
+fn compiled_plastic_networks_are_frozen() {
+    let rule = HebbianRule::new(0.5, 1.0, 0.5, 0.5, 0.5);
+    for (i, mut network) in evolved_networks(5).into_iter().enumerate() {
+        network.plasticity = Some(Plasticity::new(rule));
+        let mut compiled = network.compile();
+        let mut outputs = vec![0.0; compiled.output_count()];
+
+        // Learning happens after the pass, so the first step still agrees
+        let expected = network.forward_stateful(inputs(0));
+        compiled.forward_stateful_into(&inputs(0), &mut outputs);
+        assert_eq!(bits(&expected), bits(&outputs), "network {i}, first step");
+
+        let diverged = (1..STEPS).any(|step| {
+            let expected = network.forward_stateful(inputs(step));
+            compiled.forward_stateful_into(&inputs(step), &mut outputs);
+            bits(&expected) != bits(&outputs)
+        });
+        assert!(diverged, "network {i} kept matching after learning");
+    }
+}
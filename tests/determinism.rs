@@ -0,0 +1,49 @@
+use minet_ai::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+const GENERATIONS: usize = 25;
+
+/// Topology mutations on, so each kind of random draw is exercised
+fn config() -> EvolutionConfig {
+    EvolutionConfig {
+        add_synapse_probability: 0.3,
+        remove_synapse_probability: 0.2,
+        add_recurrent_synapse_probability: 0.2,
+        add_neuron_probability: 0.1,
+        remove_neuron_probability: 0.05,
+        activation_mutation_probability: 0.1,
+        ..Default::default()
+    }
+}
+
+/// Evolves from a seed and returns the encoded final population
+fn evolve(seed: u64) -> Vec<String> {
+    let config = config();
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut population = minet::initialize_population_with_rng(50, 3, 6, 2, &config, &mut rng);
+    for _ in 0..GENERATIONS {
+        for network in population.iter_mut() {
+            network.forward_stateful(vec![1.0, 0.0, -1.0]);
+            let outputs = network.forward_stateful(vec![0.5, -0.25, 1.0]);
+            network.fitness = outputs[0] - outputs[1];
+        }
+        population = minet::crossbreed_population_with_rng(population, 0.1, 50, &config, &mut rng);
+    }
+    population.iter().map(minet::encode).collect()
+}
+
+#[test]
+fn same_seed_evolves_identical_populations() {
+    let first = evolve(7);
+    let second = evolve(7);
+    assert_eq!(first.len(), second.len());
+    for (i, (a, b)) in first.iter().zip(&second).enumerate() {
+        assert_eq!(a, b, "member {i} differs between runs with the same seed");
+    }
+}
+
+#[test]
+fn different_seeds_evolve_different_populations() {
+    assert_ne!(evolve(7), evolve(8));
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use common::{bits, evolved_networks, inputs};
+use minet_ai::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+fn round_trip<T: serde::Serialize + serde::de::DeserializeOwned>(value: &T) -> T {
+    let json = serde_json::to_string(value).expect("serializing failed");
+    serde_json::from_str(&json).expect("deserializing failed")
+}
+
+#[test]
+fn networks_round_trip_through_json() {
+    for (i, mut network) in evolved_networks(11).into_iter().enumerate() {
+        if i % 2 == 0 {
+            network.plasticity = Some(Plasticity::new(HebbianRule::new(0.3, 0.8, -0.2, 0.1, 0.05)).with_modulator(1));
+        }
+        if i % 3 == 0 {
+            network.time_constants = (0..network.genes.len()).map(|neuron| 0.1 + 0.3 * neuron as f32).collect();
+        }
+        // Warm up the recurrent state and learned weights, which are serialized too
+        for step in 0..8 {
+            network.forward_stateful(inputs(step));
+        }
+        let mut restored = round_trip(&network);
+        assert_eq!(restored.plasticity, network.plasticity, "network {i}");
+        assert_eq!(bits(&restored.time_constants), bits(&network.time_constants), "network {i}");
+        assert_eq!(bits(&restored.state), bits(&network.state), "network {i}");
+        let changes = |network: &minet| -> Vec<u32> { network.weight_changes.iter().flat_map(|changes| bits(changes)).collect() };
+        assert_eq!(changes(&restored), changes(&network), "network {i}");
+
+        for step in 8..20 {
+            assert_eq!(bits(&restored.forward(inputs(step))), bits(&network.forward(inputs(step))));
+            assert_eq!(
+                bits(&restored.forward_stateful(inputs(step))),
+                bits(&network.forward_stateful(inputs(step))),
+                "network {i}, stateful step {step}"
+            );
+        }
+    }
+}
+
+#[test]
+fn speciated_population_round_trips_through_json() {
+    let speciation = SpeciationConfig { compatibility_threshold: 0.5, ..Default::default() };
+    let mut rng = ChaCha8Rng::seed_from_u64(12);
+    let mut population = Population::new_with_rng(30, 3, 6, 2, EvolutionConfig::default(), &mut rng).with_speciation(speciation);
+    for _ in 0..3 {
+        for network in population.members.iter_mut() {
+            network.fitness = network.forward(inputs(0))[0];
+        }
+        population.evolve_with_rng(&mut rng);
+    }
+    population.speciate();
+    assert!(population.species.len() > 1);
+
+    let mut restored = round_trip(&population);
+    assert_eq!(restored.generation, population.generation);
+    assert_eq!(restored.members.len(), population.members.len());
+    for (a, b) in restored.members.iter().zip(&population.members) {
+        assert_eq!(bits(&a.forward(inputs(1))), bits(&b.forward(inputs(1))));
+    }
+    let species = |population: &Population| -> Vec<(usize, Vec<usize>)> {
+        population.species.iter().map(|species| (species.id, species.members.clone())).collect()
+    };
+    assert_eq!(species(&restored), species(&population));
+
+    // Private bookkeeping such as the next species id must survive too, so both evolve alike
+    for population in [&mut population, &mut restored] {
+        for network in population.members.iter_mut() {
+            network.fitness = network.forward(inputs(2))[1];
+        }
+        population.evolve_with_rng(&mut ChaCha8Rng::seed_from_u64(13));
+    }
+    assert_eq!(species(&restored), species(&population));
+    let encoded = |population: &Population| -> Vec<String> { population.members.iter().map(minet::encode).collect() };
+    assert_eq!(encoded(&restored), encoded(&population));
+}